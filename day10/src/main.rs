@@ -46,13 +46,13 @@ mod tests {
         assert_example!(
             r#"
             89010123
-78121874
-87430965
-96549874
-45678903
-32019012
-01329801
-10456732
+            78121874
+            87430965
+            96549874
+            45678903
+            32019012
+            01329801
+            10456732
             "#,
             36,
             81
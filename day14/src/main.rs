@@ -1,8 +1,8 @@
-use std::str::FromStr;
+use std::{collections::HashSet, str::FromStr};
 
 use aoc_derive::aoc_main;
+use grid::{Grid, Topology};
 use itertools::Itertools;
-use lazy_regex::regex_is_match;
 use math::Vec2D;
 use utils::*;
 
@@ -13,12 +13,8 @@ struct Robot {
 }
 
 impl Robot {
-    fn move_(mut self, width: i64, height: i64) -> Self {
-        self.pos += self.velocity + (width, height);
-
-        self.pos.x %= width;
-        self.pos.y %= height;
-
+    fn move_(mut self, area: &Grid<()>) -> Self {
+        self.pos = area.step(self.pos, self.velocity).unwrap();
         self
     }
 }
@@ -32,44 +28,48 @@ impl FromStr for Robot {
     }
 }
 
-fn count_robots(robots: &[Robot], width: i64, height: i64) -> usize {
-    robots.iter().filter(|r| r.pos.x < width / 2 && r.pos.y < height / 2).count()
-        * robots.iter().filter(|r| r.pos.x < width / 2 && r.pos.y > height / 2).count()
-        * robots.iter().filter(|r| r.pos.x > width / 2 && r.pos.y < height / 2).count()
-        * robots.iter().filter(|r| r.pos.x > width / 2 && r.pos.y > height / 2).count()
+/// Robots leaving the area on one side enter it again on the opposite side
+fn robot_area(width: usize, height: usize) -> Grid<()> {
+    Grid::with_value((), height, width).with_topology(Topology::Toroidal)
+}
+
+fn count_robots(robots: &[Robot], area: &Grid<()>) -> usize {
+    let mid = Vec2D::from((area.num_cols() / 2, area.num_rows() / 2));
+    robots.iter().filter(|r| r.pos.x < mid.x && r.pos.y < mid.y).count()
+        * robots.iter().filter(|r| r.pos.x < mid.x && r.pos.y > mid.y).count()
+        * robots.iter().filter(|r| r.pos.x > mid.x && r.pos.y < mid.y).count()
+        * robots.iter().filter(|r| r.pos.x > mid.x && r.pos.y > mid.y).count()
 }
 
-fn part1(robots: impl Iterator<Item = Robot>, width: i64, height: i64) -> usize {
+fn part1(robots: impl Iterator<Item = Robot>, width: usize, height: usize) -> usize {
+    let area = robot_area(width, height);
     let robots = robots
         .into_iter()
-        .map(|robot| (0..100).fold(robot, |robot, _| robot.move_(width, height)))
+        .map(|robot| (0..100).fold(robot, |robot, _| robot.move_(&area)))
         .collect_vec();
-    count_robots(&robots, width, height)
+    count_robots(&robots, &area)
 }
 
 fn part2(mut robots: Vec<Robot>, width: usize, height: usize) -> usize {
+    let area = robot_area(width, height);
     (1..)
         .find(|_| {
-            robots =
-                robots.clone().into_iter().map(|r| r.move_(width as i64, height as i64)).collect();
+            robots = robots.clone().into_iter().map(|r| r.move_(&area)).collect();
 
-            let lines = (0..height)
-                .map(|y| {
-                    (0..width)
-                        .map(|x| if robots.iter().any(|r| r.pos == (x, y)) { 'X' } else { ' ' })
-                        .collect::<String>()
-                })
-                .collect_vec();
+            let occupied: HashSet<_> = robots.iter().map(|r| r.pos).collect();
+            let frame: Grid<bool> = (0..height)
+                .map(|y| (0..width).map(|x| occupied.contains(&Vec2D::from((x, y)))).collect_vec())
+                .collect();
 
-            if lines.iter().any(|line| regex_is_match!(r"X{10,}", line)) {
-                for line in lines {
-                    println!("{line}");
-                }
-                println!();
-                true
-            } else {
-                false
+            // The tree has a frame, so look for a long horizontal line of robots
+            let found = frame.rows().any(|row| {
+                row.map(|(_, &robot)| robot).dedup_with_count().any(|(n, robot)| robot && n >= 10)
+            });
+            // Set DAY14_TREE_IMAGE to a path to look at the christmas tree
+            if let Some(path) = std::env::var_os("DAY14_TREE_IMAGE").filter(|_| found) {
+                image::save(path, &frame.to_pgm(|&robot| if robot { 255 } else { 0 }));
             }
+            found
         })
         .unwrap()
 }
@@ -87,18 +87,18 @@ mod tests {
     #[test]
     fn test_examples() {
         let example = "
-p=0,4 v=3,-3
-p=6,3 v=-1,-3
-p=10,3 v=-1,2
-p=2,0 v=2,-1
-p=0,0 v=1,3
-p=3,0 v=-2,-2
-p=7,6 v=-1,-3
-p=3,0 v=-1,-2
-p=9,3 v=2,3
-p=7,3 v=-1,2
-p=2,4 v=2,-3
-p=9,5 v=-3,-3
+            p=0,4 v=3,-3
+            p=6,3 v=-1,-3
+            p=10,3 v=-1,2
+            p=2,0 v=2,-1
+            p=0,0 v=1,3
+            p=3,0 v=-2,-2
+            p=7,6 v=-1,-3
+            p=3,0 v=-1,-2
+            p=9,3 v=2,3
+            p=7,3 v=-1,2
+            p=2,4 v=2,-3
+            p=9,5 v=-3,-3
             ";
         assert_eq!(part1(Input::from(example).parse_lines(), 11, 7), 12);
     }
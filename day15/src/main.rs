@@ -1,9 +1,16 @@
 use aoc_derive::aoc_main;
 use grid::Grid;
 use itertools::Itertools;
-use math::Vec2D;
+use math::{Direction, Vec2D};
 use utils::*;
 
+fn headings(instructions: &str) -> impl Iterator<Item = Vec2D> + '_ {
+    instructions
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace())
+        .map(|instr| Direction::from_char(instr).unwrap().into())
+}
+
 fn try_move_part1(pos: Vec2D, heading: Vec2D, map: &mut Grid<char>) -> bool {
     match map[pos] {
         '.' => {
@@ -26,15 +33,7 @@ fn part1(mut map: Grid<char>, instructions: &str) -> i64 {
     let mut pos = map.iter().find_map(|(pos, &c)| (c == '@').then_some(pos)).unwrap();
     map[pos] = '.';
 
-    for heading in instructions.chars().filter(|c| !c.is_ascii_whitespace()).map(|instr| {
-        Vec2D::from(match instr {
-            'v' => (0, 1),
-            '>' => (1, 0),
-            '<' => (-1, 0),
-            '^' => (0, -1),
-            _ => unreachable!(),
-        })
-    }) {
+    for heading in headings(instructions) {
         if try_move_part1(pos + heading, heading, &mut map) {
             pos += heading;
         }
@@ -44,15 +43,8 @@ fn part1(mut map: Grid<char>, instructions: &str) -> i64 {
 }
 
 fn try_move_horizontally_part2(pos: Vec2D, heading: Vec2D, map: &mut Grid<char>) -> bool {
-    if let Some(dot) = (1..)
-        .find_map(|i| match map[pos + i * heading] {
-            '.' => Some(Some(i)),
-            '#' => Some(None),
-            _ => None,
-        })
-        .unwrap()
-    {
-        for j in (0..dot).rev() {
+    if let Some((dot, '.')) = map.cast(pos, heading, |&c| c == '.' || c == '#') {
+        for j in (0..(dot - pos).manhattan_dist()).rev() {
             map.swap(pos + (j + 1) * heading, pos + j * heading);
         }
         return true;
@@ -126,15 +118,7 @@ fn part2(map: &str, instructions: &str) -> i64 {
     let mut pos = map.iter().find_map(|(pos, &c)| (c == '@').then_some(pos)).unwrap();
     map[pos] = '.';
 
-    for heading in instructions.chars().filter(|c| !c.is_ascii_whitespace()).map(|instr| {
-        Vec2D::from(match instr {
-            'v' => (0, 1),
-            '>' => (1, 0),
-            '<' => (-1, 0),
-            '^' => (0, -1),
-            _ => unreachable!(),
-        })
-    }) {
+    for heading in headings(instructions) {
         if heading.y == 0 && try_move_horizontally_part2(pos, heading, &mut map)
             || try_move_vertically_part2(pos, heading, &mut map)
         {
@@ -1,39 +1,31 @@
 use std::collections::{HashMap, HashSet};
 
 use aoc_derive::aoc_main;
-use graphs::{WeightedGraph, dijkstra};
+use graphs::{Cost, dijkstra, weighted_graph_fn};
 use grid::Grid;
 use itertools::Itertools;
-use math::Vec2D;
+use math::{Direction, Vec2D};
 use utils::*;
 
-#[derive(Debug, Clone, derive_more::Into, derive_more::Deref)]
-struct Maze(Grid<char>);
+type Node = (Vec2D, Direction);
 
-impl WeightedGraph for Maze {
-    type Node = (Vec2D, Vec2D);
-
-    fn neighbors<'a, 'b: 'a>(
-        &'a self,
-        (pos, heading): &'b Self::Node,
-    ) -> impl Iterator<Item = (Self::Node, graphs::Cost)> + 'a {
-        self.orthogonal_neighbors(pos).filter_map(|neighbor| {
-            (self[neighbor] != '#').then_some((
-                (neighbor, *pos - neighbor),
-                1 + if *pos - neighbor == *heading { 0 } else { 1000 },
-            ))
-        })
-    }
+/// Moving forward costs 1, turning costs 1000
+fn neighbors(maze: &Grid<char>, &(pos, heading): &Node) -> Vec<(Node, Cost)> {
+    Direction::ORTHOGONAL
+        .into_iter()
+        .filter(|&dir| maze.get(pos + dir).is_some_and(|&c| c != '#'))
+        .map(|dir| ((pos + dir, dir), 1 + if dir == heading { 0 } else { 1000 }))
+        .collect()
 }
 
 fn all_paths(
-    maze: &Maze,
+    maze: &Grid<char>,
     pos: Vec2D,
-    heading: Vec2D,
+    heading: Direction,
     score: usize,
     max_score: usize,
     mut path: HashSet<Vec2D>,
-    visited: &mut HashMap<(Vec2D, Vec2D), usize>,
+    visited: &mut HashMap<Node, usize>,
 ) -> HashSet<Vec2D> {
     // This is the main optimization that makes DFS work here:
     // If we already visited this node with a lower score, stop the search
@@ -54,7 +46,8 @@ fn all_paths(
         return path;
     }
 
-    maze.neighbors(&(pos, heading))
+    neighbors(maze, &(pos, heading))
+        .into_iter()
         // Don't go backwards
         .filter(|((pos, _), _)| !path.contains(pos))
         // Sort ascending, so that we try to go forward before doing a turn
@@ -75,11 +68,12 @@ fn all_paths(
 
 #[aoc_main]
 fn solve(input: Input) -> impl Into<Solution> {
-    let maze = Maze(input.char_grid());
+    let maze = input.char_grid();
 
-    let (start, heading) = (maze.find_position(&'S').unwrap(), Vec2D::new(1, 0));
+    let (start, heading) = (maze.find_position(&'S').unwrap(), Direction::East);
 
-    let part1 = dijkstra(&maze, [(start, heading)], |(pos, _)| maze[*pos] == 'E').unwrap();
+    let graph = weighted_graph_fn(|node| neighbors(&maze, node));
+    let part1 = dijkstra(&graph, [(start, heading)], |(pos, _)| maze[*pos] == 'E').unwrap();
 
     let part2 =
         all_paths(&maze, start, heading, 0, part1, HashSet::new(), &mut HashMap::new()).len();
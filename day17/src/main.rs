@@ -22,9 +22,9 @@ impl FromStr for Vm {
         Ok(Self {
             memory: extract_numbers(lines[4]).collect(),
             ip: 0,
-            ra: extract_numbers_unsigned(lines[0]).next().unwrap(),
-            rb: extract_numbers_unsigned(lines[1]).next().unwrap(),
-            rc: extract_numbers_unsigned(lines[2]).next().unwrap(),
+            ra: extract_unsigned_numbers(lines[0]).next().unwrap(),
+            rb: extract_unsigned_numbers(lines[1]).next().unwrap(),
+            rc: extract_unsigned_numbers(lines[2]).next().unwrap(),
         })
     }
 }
@@ -1,26 +1,13 @@
 use std::iter::repeat_n;
 
 use aoc_derive::aoc_main;
-use derive_more::derive::{Deref, DerefMut, From};
-use graphs::{UnweightedGraph, bfs};
+use graphs::bfs;
 use grid::Grid;
-use math::Vec2D;
 use utils::*;
 
-#[derive(Debug, Clone, Deref, DerefMut, From)]
-struct Map(Grid<char>);
-
-impl UnweightedGraph for Map {
-    type Node = Vec2D;
-
-    fn neighbors<'a, 'b: 'a>(&'a self, node: &'b Vec2D) -> impl Iterator<Item = Vec2D> + 'a {
-        self.orthogonal_neighbors(node).filter(|n| self.get(*n) == Some(&'.'))
-    }
-}
-
 #[aoc_main(70, 1024)]
 fn solve(input: Input, size: usize, num_bytes: usize) -> impl Into<Solution> {
-    let mut map = Map::from(repeat_n(repeat_n('.', size + 1), size + 1).collect::<Grid<char>>());
+    let mut map: Grid<char> = repeat_n(repeat_n('.', size + 1), size + 1).collect();
     let mut bytes = input.lines().map(extract_two_numbers);
 
     let (start, end) = ((0, 0), (size, size));
@@ -31,13 +18,13 @@ fn solve(input: Input, size: usize, num_bytes: usize) -> impl Into<Solution> {
             map[byte] = '#';
         }
 
-        bfs(&map, start, end).distance.unwrap()
+        bfs(&map.as_graph(|_, &to| to == '.'), start, end).distance.unwrap()
     };
 
     let part2 = bytes
         .find(|&byte| {
             map[byte] = '#';
-            bfs(&map, start, end).distance.is_none()
+            bfs(&map.as_graph(|_, &to| to == '.'), start, end).distance.is_none()
         })
         .unwrap();
 
@@ -1,31 +1,19 @@
 use aoc_derive::aoc_main;
-use derive_more::derive::{Deref, DerefMut, From};
-use graphs::{UnweightedGraph, bfs, floodfill};
-use grid::Grid;
+use graphs::{bfs, floodfill};
 use math::Vec2D;
 use utils::*;
 
-#[derive(Debug, Clone, From, Deref, DerefMut)]
-struct Racetrack(Grid<char>);
-
-impl UnweightedGraph for Racetrack {
-    type Node = Vec2D;
-
-    fn neighbors<'a, 'b: 'a>(&'a self, node: &'b Vec2D) -> impl Iterator<Item = Vec2D> + 'a {
-        self.orthogonal_neighbors(node).filter(|&n| self[n] != '#')
-    }
-}
-
 #[aoc_main(100)]
 fn solve(input: Input, min_save: usize) -> impl Into<Solution> {
-    let track: Racetrack = input.char_grid().into();
+    let track = input.char_grid();
     let start = track.find_position(&'S').unwrap();
     let end = track.find_position(&'E').unwrap();
 
-    let best_without_cheat = bfs(&track, start, end).distance.unwrap();
+    let graph = track.as_graph(|_, &to| to != '#');
+    let best_without_cheat = bfs(&graph, start, end).distance.unwrap();
 
-    let distance_from_start = floodfill(&track, start);
-    let distance_from_end = floodfill(&track, end);
+    let distance_from_start = floodfill(&graph, start);
+    let distance_from_end = floodfill(&graph, end);
 
     let find_cheats = |pos: Vec2D, max_cheat: usize| {
         track
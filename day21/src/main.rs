@@ -3,7 +3,7 @@ use std::{collections::BTreeMap, iter::once};
 use aoc_derive::aoc_main;
 use graphs::{WeightedGraph, dijkstra};
 use itertools::Itertools;
-use math::Vec2D;
+use math::{Direction, Vec2D};
 use utils::*;
 
 // Order matters here! e.g., changing >^ to ^> changes the shortest path to the code.
@@ -124,21 +124,19 @@ impl WeightedGraph for KeypadRobot {
         if has_pushed {
             vec![].into_iter()
         } else {
-            [
-                Some(((pos, 'A', true), self.expand_move(keypad_pos, 'A'))),
-                (pos.x < 2)
-                    .then_some(((pos + (1, 0), '>', false), self.expand_move(keypad_pos, '>'))),
-                (pos.x > 0 && pos != (1, 3))
-                    .then_some(((pos + (-1, 0), '<', false), self.expand_move(keypad_pos, '<'))),
-                (pos.y < 3 && pos != (0, 2))
-                    .then_some(((pos + (0, 1), 'v', false), self.expand_move(keypad_pos, 'v'))),
-                (pos.y > 0)
-                    .then_some(((pos + (0, -1), '^', false), self.expand_move(keypad_pos, '^'))),
-            ]
-            .into_iter()
-            .flatten()
-            .collect_vec()
-            .into_iter()
+            once(((pos, 'A', true), self.expand_move(keypad_pos, 'A')))
+                .chain(
+                    Direction::ORTHOGONAL
+                        .into_iter()
+                        .map(|dir| (pos + dir, dir.to_arrow()))
+                        // Stay on the keypad and away from the gap in its lower left corner
+                        .filter(|&(next, _)| next.inside_box((0, 0), (2, 3)) && next != (0, 3))
+                        .map(|(next, arrow)| {
+                            ((next, arrow, false), self.expand_move(keypad_pos, arrow))
+                        }),
+                )
+                .collect_vec()
+                .into_iter()
         }
     }
 }
@@ -1,40 +1,16 @@
 use aoc_derive::aoc_main;
+use grid::Grid;
+use math::Vec2D;
 use utils::*;
 
 #[aoc_main]
 fn solve(input: Input) -> impl Into<Solution> {
     let grid = input.char_grid();
 
-    let mut part1 = 0;
-    for x in 0..grid.num_cols() {
-        for y in 0..grid.num_rows() {
-            for inc_i in [-1_i64, 0, 1_i64] {
-                for inc_j in [-1_i64, 0, 1_i64] {
-                    if grid.get((x, y)) == Some(&'X')
-                        && grid.get((x as i64 + inc_i, y as i64 + inc_j)) == Some(&'M')
-                        && grid.get((x as i64 + 2 * inc_i, y as i64 + 2 * inc_j)) == Some(&'A')
-                        && grid.get((x as i64 + 3 * inc_i, y as i64 + 3 * inc_j)) == Some(&'S')
-                    {
-                        part1 += 1;
-                    }
-                }
-            }
-        }
-    }
+    let part1 = grid.find_sequence(&['X', 'M', 'A', 'S'], Vec2D::zero().all_neighbors()).count();
 
-    let mut part2 = 0;
-    for x in 0..grid.num_cols() {
-        for y in 0..grid.num_rows() {
-            if grid.get((x + 1, y + 1)) == Some(&'A')
-                && (grid.get((x, y)) == Some(&'M') && grid.get((x + 2, y + 2)) == Some(&'S')
-                    || grid.get((x, y)) == Some(&'S') && grid.get((x + 2, y + 2)) == Some(&'M'))
-                && (grid.get((x + 2, y)) == Some(&'M') && grid.get((x, y + 2)) == Some(&'S')
-                    || grid.get((x + 2, y)) == Some(&'S') && grid.get((x, y + 2)) == Some(&'M'))
-            {
-                part2 += 1;
-            }
-        }
-    }
+    let x_mas = Grid::from(["M.S", ".A.", "M.S"].map(str::chars)).to_template(&'.');
+    let part2 = grid.find_template_any_orientation(&x_mas).len();
 
     (part1, part2)
 }
@@ -1,28 +1,29 @@
 use std::collections::HashSet;
 
 use aoc_derive::aoc_main;
+use cycle::find_cycle_brent;
 use grid::Grid;
-use itertools::Itertools;
-use math::Vec2D;
+use math::{Direction, Vec2D};
 use utils::*;
 
-fn iterate(map: &Grid<char>, start: Vec2D) -> Option<HashSet<(Vec2D, Vec2D)>> {
-    let mut pos = start;
-    let mut heading = Vec2D::new(0, -1);
-    let mut visited = HashSet::new();
-
-    loop {
-        if visited.contains(&(pos, heading)) {
-            return None; // loop detected
-        }
-        visited.insert((pos, heading));
-        match map.get(pos + heading) {
-            None => break,
-            Some(&'#') => heading = heading.rotated_right(),
-            _ => pos += heading,
-        }
+/// `None` once the guard has left the map
+fn step(map: &Grid<char>, guard: &Option<(Vec2D, Direction)>) -> Option<(Vec2D, Direction)> {
+    let (pos, heading) = (*guard)?;
+    match map.get(pos + heading)? {
+        '#' => Some((pos, heading.turn_right())),
+        _ => Some((pos + heading, heading)),
     }
-    Some(visited)
+}
+
+fn visited(map: &Grid<char>, start: Vec2D) -> HashSet<Vec2D> {
+    std::iter::successors(Some((start, Direction::North)), |&guard| step(map, &Some(guard)))
+        .map(|(pos, _)| pos)
+        .collect()
+}
+
+/// Leaving the map ends in the fixed point `None`, every loop consists of at least 4 states
+fn loops(map: &Grid<char>, start: Vec2D) -> bool {
+    find_cycle_brent(Some((start, Direction::North)), |guard| step(map, guard)).length > 1
 }
 
 #[aoc_main]
@@ -31,8 +32,7 @@ fn solve(input: Input) -> impl Into<Solution> {
 
     let start = map.iter().find_map(|(pos, &c)| (c == '^').then_some(pos)).unwrap();
 
-    let visited: HashSet<_> =
-        iterate(&map, start).unwrap().into_iter().map(|(pos, _)| pos).unique().collect();
+    let visited = visited(&map, start);
 
     let part1 = visited.len();
 
@@ -40,9 +40,9 @@ fn solve(input: Input) -> impl Into<Solution> {
         .into_iter()
         .filter(|&pos| {
             map[pos] = '#';
-            let res = iterate(&map, start);
+            let loops = loops(&map, start);
             map[pos] = '.';
-            res.is_none()
+            loops
         })
         .count();
 
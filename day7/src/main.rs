@@ -30,7 +30,7 @@ fn solve(input: Input) -> impl Into<Solution> {
         .lines()
         .map(|line| {
             let (target, numbers) = line.split_once(": ").unwrap();
-            let numbers = extract_numbers_unsigned(numbers);
+            let numbers = extract_unsigned_numbers::<usize>(numbers);
             (target.parse::<usize>().unwrap(), numbers.collect_vec())
         })
         .collect_vec();
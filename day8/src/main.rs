@@ -7,13 +7,7 @@ use math::Vec2D;
 use utils::*;
 
 fn antinodes_part2(a: Vec2D, b: Vec2D, grid: &Grid<char>) -> impl Iterator<Item = Vec2D> {
-    let mut antinodes = vec![];
-    let mut pos = a;
-    while grid.contains(&pos) {
-        antinodes.push(pos);
-        pos += a - b;
-    }
-    antinodes.into_iter()
+    grid.ray(a, a - b).map(|(pos, _)| pos)
 }
 
 #[aoc_main]
@@ -0,0 +1,342 @@
+//! Step loop for cellular automata (Game of Life variants, spreading, erosion, ...) on a [`Grid`]
+//! or a [`SparseGrid`]. The rule sees one [`Cell`] at a time and returns its next value, all cells
+//! are updated simultaneously.
+//!
+//! With the `rayon` feature, [`Automaton::parallel`] computes the cells of a step in parallel.
+
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use itertools::Itertools;
+
+use crate::grid::Grid;
+use crate::math::{Direction, Vec2D};
+use crate::sparse_grid::SparseGrid;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// `Sync` if the `rayon` feature is enabled, implemented for all types otherwise
+#[cfg(feature = "rayon")]
+pub trait MaybeSync: Sync {}
+#[cfg(feature = "rayon")]
+impl<T: Sync> MaybeSync for T {}
+#[cfg(not(feature = "rayon"))]
+pub trait MaybeSync {}
+#[cfg(not(feature = "rayon"))]
+impl<T> MaybeSync for T {}
+
+/// `Send` if the `rayon` feature is enabled, implemented for all types otherwise
+#[cfg(feature = "rayon")]
+pub trait MaybeSend: Send {}
+#[cfg(feature = "rayon")]
+impl<T: Send> MaybeSend for T {}
+#[cfg(not(feature = "rayon"))]
+pub trait MaybeSend {}
+#[cfg(not(feature = "rayon"))]
+impl<T> MaybeSend for T {}
+
+/// A cell of the current state, passed to the rule
+#[derive(Debug)]
+pub struct Cell<'a, S> {
+    pos: Vec2D,
+    space: &'a S,
+}
+
+impl<S> Clone for Cell<'_, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S> Copy for Cell<'_, S> {}
+
+impl<S> Cell<'_, S> {
+    pub fn pos(&self) -> Vec2D {
+        self.pos
+    }
+}
+
+impl<'a, T> Cell<'a, Grid<T>> {
+    pub fn value(&self) -> &'a T {
+        &self.space[self.pos]
+    }
+
+    pub fn grid(&self) -> &'a Grid<T> {
+        self.space
+    }
+
+    /// Values of the 8 surrounding cells, respecting the grid's topology
+    pub fn neighbors(&self) -> impl Iterator<Item = &'a T> {
+        self.neighbors_in(Direction::ALL)
+    }
+
+    pub fn orthogonal_neighbors(&self) -> impl Iterator<Item = &'a T> {
+        self.neighbors_in(Direction::ORTHOGONAL)
+    }
+
+    /// Number of the 8 surrounding cells matching `pred`
+    pub fn count_neighbors(&self, pred: impl Fn(&T) -> bool) -> usize {
+        self.neighbors().filter(|&n| pred(n)).count()
+    }
+
+    fn neighbors_in<const N: usize>(
+        &self,
+        directions: [Direction; N],
+    ) -> impl Iterator<Item = &'a T> {
+        let (pos, grid) = (self.pos, self.space);
+        directions.into_iter().filter_map(move |dir| grid.get(pos + dir))
+    }
+}
+
+impl<'a, T: Hash> Cell<'a, SparseGrid<T>> {
+    /// `None` if the cell is empty
+    pub fn value(&self) -> Option<&'a T> {
+        self.space.get(self.pos)
+    }
+
+    pub fn grid(&self) -> &'a SparseGrid<T> {
+        self.space
+    }
+
+    /// Values of the non-empty cells among the 8 surrounding ones
+    pub fn neighbors(&self) -> impl Iterator<Item = &'a T> {
+        self.neighbors_in(Direction::ALL)
+    }
+
+    pub fn orthogonal_neighbors(&self) -> impl Iterator<Item = &'a T> {
+        self.neighbors_in(Direction::ORTHOGONAL)
+    }
+
+    /// Number of non-empty surrounding cells matching `pred`
+    pub fn count_neighbors(&self, pred: impl Fn(&T) -> bool) -> usize {
+        self.neighbors().filter(|&n| pred(n)).count()
+    }
+
+    fn neighbors_in<const N: usize>(
+        &self,
+        directions: [Direction; N],
+    ) -> impl Iterator<Item = &'a T> {
+        let (pos, grid) = (self.pos, self.space);
+        directions.into_iter().filter_map(move |dir| grid.get(pos + dir))
+    }
+}
+
+/// A state an [`Automaton`] can step. `Value` is what the rule returns for a cell.
+pub trait Space: Clone + PartialEq + MaybeSync {
+    type Value;
+
+    /// Writes the next state into `next`, which is a previous state of the same automaton
+    fn step_into<R>(&self, next: &mut Self, rule: &R, parallel: bool)
+    where
+        R: Fn(Cell<'_, Self>) -> Self::Value + MaybeSync;
+}
+
+impl<T> Space for Grid<T>
+where
+    T: Clone + PartialEq + MaybeSync + MaybeSend,
+{
+    type Value = T;
+
+    fn step_into<R>(&self, next: &mut Self, rule: &R, parallel: bool)
+    where
+        R: Fn(Cell<'_, Self>) -> T + MaybeSync,
+    {
+        let num_cols = self.num_cols();
+        let cell = |i: usize| Cell { pos: (i % num_cols, i / num_cols).into(), space: self };
+        let next = next.as_mut_slice();
+
+        #[cfg(feature = "rayon")]
+        if parallel {
+            next.par_iter_mut().enumerate().for_each(|(i, out)| *out = rule(cell(i)));
+            return;
+        }
+        #[cfg(not(feature = "rayon"))]
+        let _ = parallel;
+
+        for (i, out) in next.iter_mut().enumerate() {
+            *out = rule(cell(i));
+        }
+    }
+}
+
+/// Only non-empty cells and their neighbors are visited, so the rule must return `None` for an
+/// empty cell without non-empty neighbors
+impl<T> Space for SparseGrid<T>
+where
+    T: Clone + PartialEq + Hash + MaybeSync + MaybeSend,
+{
+    type Value = Option<T>;
+
+    fn step_into<R>(&self, next: &mut Self, rule: &R, parallel: bool)
+    where
+        R: Fn(Cell<'_, Self>) -> Option<T> + MaybeSync,
+    {
+        let candidates: HashSet<Vec2D> =
+            self.keys().flat_map(|&pos| pos.all_neighbors().chain([pos]).collect_vec()).collect();
+        let cell = |pos: Vec2D| rule(Cell { pos, space: self }).map(|value| (pos, value));
+        next.clear();
+
+        #[cfg(feature = "rayon")]
+        if parallel {
+            let cells: Vec<_> = candidates.into_par_iter().filter_map(cell).collect();
+            next.extend(cells);
+            return;
+        }
+        #[cfg(not(feature = "rayon"))]
+        let _ = parallel;
+
+        next.extend(candidates.into_iter().filter_map(cell));
+    }
+}
+
+/// Runs a rule on a [`Grid`] or [`SparseGrid`] with double buffering, i.e. the two states are
+/// swapped after every step instead of allocating a new one
+pub struct Automaton<S, R> {
+    state: S,
+    next: S,
+    rule: R,
+    parallel: bool,
+    steps: usize,
+}
+
+impl<S, R> Automaton<S, R>
+where
+    S: Space,
+    R: Fn(Cell<'_, S>) -> S::Value + MaybeSync,
+{
+    pub fn new(initial: S, rule: R) -> Self {
+        Self { next: initial.clone(), state: initial, rule, parallel: false, steps: 0 }
+    }
+
+    /// Computes the cells of each step in parallel
+    #[cfg(feature = "rayon")]
+    pub fn parallel(mut self) -> Self {
+        self.parallel = true;
+        self
+    }
+
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    pub fn into_state(self) -> S {
+        self.state
+    }
+
+    /// Total number of steps performed so far
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Returns whether the state changed
+    pub fn step(&mut self) -> bool {
+        self.state.step_into(&mut self.next, &self.rule, self.parallel);
+        std::mem::swap(&mut self.state, &mut self.next);
+        self.steps += 1;
+        self.state != self.next
+    }
+
+    /// Performs `n` steps and returns `n`
+    pub fn run(&mut self, n: usize) -> usize {
+        for _ in 0..n {
+            self.step();
+        }
+        n
+    }
+
+    /// Steps until a step doesn't change the state. Returns the number of steps performed,
+    /// including that last one (e.g. "the first round in which nothing moves").
+    pub fn run_until_fixed_point(&mut self) -> usize {
+        let mut steps = 1;
+        while self.step() {
+            steps += 1;
+        }
+        steps
+    }
+
+    /// Steps until the state equals any earlier one. Returns the number of steps performed.
+    pub fn run_until_repeat(&mut self) -> usize
+    where
+        S: Eq + Hash,
+    {
+        let mut seen = HashSet::from([self.state.clone()]);
+        let mut steps = 0;
+        loop {
+            self.step();
+            steps += 1;
+            if !seen.insert(self.state.clone()) {
+                return steps;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::char_grid;
+
+    use super::*;
+
+    fn life(cell: Cell<'_, Grid<char>>) -> char {
+        match (cell.value(), cell.count_neighbors(|&c| c == '#')) {
+            ('#', 2 | 3) | ('.', 3) => '#',
+            _ => '.',
+        }
+    }
+
+    #[test]
+    fn blinker() {
+        let vertical = char_grid(".....\n..#..\n..#..\n..#..\n.....");
+        let horizontal = char_grid(".....\n.....\n.###.\n.....\n.....");
+
+        let mut automaton = Automaton::new(vertical.clone(), life);
+        assert!(automaton.step());
+        assert_eq!(automaton.state(), &horizontal);
+        assert_eq!(automaton.run(3), 3);
+        assert_eq!(automaton.state(), &vertical);
+        assert_eq!(automaton.steps(), 4);
+
+        assert_eq!(Automaton::new(vertical, life).run_until_repeat(), 2);
+    }
+
+    #[test]
+    fn fixed_point() {
+        // Fire spreads orthogonally into trees until everything reachable has burnt down
+        let forest = char_grid("*TT.T\nT.T.T\n..TTT");
+        let mut automaton = Automaton::new(forest, |cell| match cell.value() {
+            'T' if cell.orthogonal_neighbors().any(|&n| n == '*') => '*',
+            '*' => '_',
+            &c => c,
+        });
+        assert_eq!(automaton.run_until_fixed_point(), 10);
+        assert_eq!(automaton.into_state(), char_grid("___._\n_._._\n..___"));
+    }
+
+    #[test]
+    fn sparse_glider() {
+        let glider: SparseGrid<()> =
+            [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)].into_iter().map(|pos| (pos, ())).collect();
+        let mut automaton =
+            Automaton::new(glider.clone(), |cell| match (cell.value(), cell.neighbors().count()) {
+                (Some(_), 2 | 3) | (None, 3) => Some(()),
+                _ => None,
+            });
+        automaton.run(4);
+        let moved: SparseGrid<()> = glider.keys().map(|&pos| (pos + (1, 1), ())).collect();
+        assert_eq!(automaton.state(), &moved);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel() {
+        let grid = char_grid("......\n.##...\n.##...\n...##.\n...##.\n......");
+        let mut sequential = Automaton::new(grid.clone(), life);
+        let mut parallel = Automaton::new(grid, life).parallel();
+        for _ in 0..5 {
+            sequential.step();
+            parallel.step();
+            assert_eq!(sequential.state(), parallel.state());
+        }
+    }
+}
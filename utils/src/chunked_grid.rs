@@ -0,0 +1,291 @@
+use std::cell::OnceCell;
+use std::collections::HashMap;
+use std::ops::{Index, IndexMut};
+
+use crate::grid::Grid;
+use crate::math::{Box2D, Vec2D};
+
+/// Width and height of a chunk
+pub const CHUNK_SIZE: i64 = 64;
+
+/// Infinite grid where every cell starts out as `default`. Cells are allocated in chunks of
+/// [`CHUNK_SIZE`]x[`CHUNK_SIZE`] the first time one of them is written, so the grid grows in any
+/// direction (including negative coordinates) without copying.
+///
+/// Two grids are equal if they have the same default and the same cells with other values,
+/// regardless of which chunks are allocated.
+#[derive(Debug, Clone)]
+pub struct ChunkedGrid<T> {
+    chunks: HashMap<Vec2D, Box<[T]>>,
+    default: T,
+    /// Smallest box containing all cells written with [`ChunkedGrid::set`]
+    written: Option<Box2D>,
+    /// Smallest box containing all cells that differ from `default`. Computed lazily, since a
+    /// mutable reference might not be used for writing at all.
+    non_default: OnceCell<Option<Box2D>>,
+}
+
+fn union(lhs: Option<Box2D>, rhs: Option<Box2D>) -> Option<Box2D> {
+    match (lhs, rhs) {
+        (Some(mut lhs), Some(rhs)) => {
+            lhs.extend(rhs.lower);
+            lhs.extend(rhs.upper);
+            Some(lhs)
+        }
+        (lhs, rhs) => lhs.or(rhs),
+    }
+}
+
+/// The chunk containing `pos` and the index of `pos` inside that chunk
+fn chunk_index(pos: Vec2D) -> (Vec2D, usize) {
+    let chunk = Vec2D::new(pos.x.div_euclid(CHUNK_SIZE), pos.y.div_euclid(CHUNK_SIZE));
+    let local = pos.rem_euclid((CHUNK_SIZE, CHUNK_SIZE));
+    (chunk, (local.y * CHUNK_SIZE + local.x) as usize)
+}
+
+impl<T: Clone> ChunkedGrid<T> {
+    pub fn new(default: T) -> Self {
+        Self { chunks: HashMap::new(), default, written: None, non_default: OnceCell::new() }
+    }
+
+    /// Allocates the chunk containing `pos` if needed. Only extends the bounds if the cell is
+    /// changed to a value other than the default, use [`ChunkedGrid::set`] to always extend them.
+    pub fn get_mut(&mut self, pos: impl Into<Vec2D>) -> &mut T {
+        self.non_default.take();
+        let (chunk, index) = chunk_index(pos.into());
+        let default = &self.default;
+        let chunk = self.chunks.entry(chunk).or_insert_with(|| {
+            vec![default.clone(); (CHUNK_SIZE * CHUNK_SIZE) as usize].into_boxed_slice()
+        });
+        &mut chunk[index]
+    }
+
+    /// Always extends the bounds, even if `value` is the default
+    pub fn set(&mut self, pos: impl Into<Vec2D>, value: T) {
+        let pos = pos.into();
+        self.written = union(self.written.take(), Some(Box2D::new(pos, pos)));
+        *self.get_mut(pos) = value;
+    }
+}
+
+impl<T: Clone + PartialEq> ChunkedGrid<T> {
+    /// Dense copy of the cells inside [`ChunkedGrid::bounds`], whose `(0, 0)` is the lower corner
+    /// of the bounds
+    pub fn to_grid(&self) -> Grid<T> {
+        let num_cols = self.bounds().map_or(0, |b| (b.upper.x - b.lower.x + 1) as usize);
+        Grid::from_flat(self.iter().map(|(_, item)| item.clone()).collect(), num_cols)
+    }
+}
+
+impl<T> ChunkedGrid<T> {
+    /// Never fails, cells that were never written have the default value
+    pub fn get(&self, pos: impl Into<Vec2D>) -> &T {
+        let (chunk, index) = chunk_index(pos.into());
+        self.chunks.get(&chunk).map_or(&self.default, |chunk| &chunk[index])
+    }
+
+    pub fn num_chunks(&self) -> usize {
+        self.chunks.len()
+    }
+}
+
+impl<T: PartialEq> ChunkedGrid<T> {
+    /// Smallest box containing all cells that have been written with [`ChunkedGrid::set`] or that
+    /// differ from the default, `None` if there are none
+    pub fn bounds(&self) -> Option<Box2D> {
+        let non_default = self.non_default.get_or_init(|| {
+            self.non_default_cells()
+                .fold(None, |bounds, (pos, _)| union(bounds, Some(Box2D::new(pos, pos))))
+        });
+        union(self.written.clone(), non_default.clone())
+    }
+
+    /// All cells that differ from the default, in no particular order
+    fn non_default_cells(&self) -> impl Iterator<Item = (Vec2D, &T)> + '_ {
+        self.chunks.iter().flat_map(move |(chunk, cells)| {
+            cells.iter().enumerate().filter(|(_, item)| **item != self.default).map(
+                move |(index, item)| {
+                    let local = (index as i64 % CHUNK_SIZE, index as i64 / CHUNK_SIZE);
+                    (*chunk * CHUNK_SIZE + local, item)
+                },
+            )
+        })
+    }
+
+    pub fn coordinates_row_major(&self) -> impl Iterator<Item = Vec2D> {
+        self.bounds().into_iter().flat_map(|Box2D { lower, upper }| {
+            (lower.y..=upper.y)
+                .flat_map(move |y| (lower.x..=upper.x).map(move |x| Vec2D::new(x, y)))
+        })
+    }
+
+    /// All cells inside the bounds in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2D, &T)> + '_ {
+        self.coordinates_row_major().map(|pos| (pos, self.get(pos)))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = (Vec2D, &T)> + '_> + '_ {
+        self.bounds().into_iter().flat_map(move |Box2D { lower, upper }| {
+            (lower.y..=upper.y).map(move |y| {
+                (lower.x..=upper.x).map(move |x| (Vec2D::new(x, y), self.get((x, y))))
+            })
+        })
+    }
+}
+
+impl<T: PartialEq> PartialEq for ChunkedGrid<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.default == other.default
+            && self.non_default_cells().all(|(pos, item)| other.get(pos) == item)
+            && other.non_default_cells().all(|(pos, item)| self.get(pos) == item)
+    }
+}
+
+impl<T: Eq> Eq for ChunkedGrid<T> {}
+
+impl<T: PartialEq> ChunkedGrid<T> {
+    pub fn find_position(&self, val: &T) -> Option<Vec2D> {
+        self.iter().find_map(|(pos, v)| (v == val).then_some(pos))
+    }
+}
+
+impl<T: Clone> ChunkedGrid<T> {
+    /// Copies all cells of `grid`, everything outside of it is `default`
+    pub fn from_grid(grid: &Grid<T>, default: T) -> Self {
+        let mut chunked = Self::new(default);
+        for (pos, item) in grid.iter() {
+            chunked.set(pos, item.clone());
+        }
+        chunked
+    }
+}
+
+impl<T: Clone + Default> From<&Grid<T>> for ChunkedGrid<T> {
+    /// Everything outside of the grid is `T::default()`, see [`ChunkedGrid::from_grid`] for other
+    /// defaults
+    fn from(grid: &Grid<T>) -> Self {
+        Self::from_grid(grid, T::default())
+    }
+}
+
+impl<T, Pos> Index<Pos> for ChunkedGrid<T>
+where
+    Pos: Into<Vec2D>,
+{
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.get(pos)
+    }
+}
+
+impl<T: Clone, Pos> IndexMut<Pos> for ChunkedGrid<T>
+where
+    Pos: Into<Vec2D>,
+{
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        self.get_mut(pos)
+    }
+}
+
+impl<T> std::fmt::Display for ChunkedGrid<T>
+where
+    T: std::fmt::Display + PartialEq,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for (_, x) in row {
+                write!(f, "{x}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    #[test]
+    fn grows_in_all_directions() {
+        let mut grid = ChunkedGrid::new('.');
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid[(1000, -1000)], '.');
+        assert_eq!(grid.iter().count(), 0);
+
+        grid[(0, 0)] = '#';
+        grid.set((-1, 2), '@');
+        assert_eq!(grid.bounds(), Some(Box2D::new((-1, 0), (0, 2))));
+        assert_eq!(grid.num_chunks(), 2);
+        assert_eq!(grid.to_string(), ".#\n..\n@.\n");
+        assert_eq!(grid.find_position(&'@'), Some(Vec2D::new(-1, 2)));
+
+        grid.set((CHUNK_SIZE - 1, -CHUNK_SIZE), 'x');
+        assert_eq!(grid.num_chunks(), 3);
+        assert_eq!(grid[(CHUNK_SIZE - 1, -CHUNK_SIZE)], 'x');
+        assert_eq!(grid[(CHUNK_SIZE, -CHUNK_SIZE)], '.');
+        assert_eq!(grid.bounds(), Some(Box2D::new((-1, -CHUNK_SIZE), (CHUNK_SIZE - 1, 2))));
+        assert_eq!(grid.iter().count(), ((CHUNK_SIZE + 1) * (CHUNK_SIZE + 3)) as usize);
+    }
+
+    #[test]
+    fn iteration() {
+        let mut grid = ChunkedGrid::new(0);
+        grid.set((1, -1), 1);
+        grid.set((2, 0), 2);
+        assert_eq!(
+            grid.iter().collect_vec(),
+            [
+                (Vec2D::new(1, -1), &1),
+                (Vec2D::new(2, -1), &0),
+                (Vec2D::new(1, 0), &0),
+                (Vec2D::new(2, 0), &2)
+            ]
+        );
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.to_grid(), Grid::from([[1, 0], [0, 2]]));
+        assert_eq!(ChunkedGrid::new(0).to_grid().num_rows(), 0);
+    }
+
+    #[test]
+    fn from_grid() {
+        let grid: Grid<_> = [['#', '.'], ['#', '.']].into();
+        let mut chunked = ChunkedGrid::from_grid(&grid, '.');
+        assert_eq!(chunked.to_grid(), grid);
+        assert_eq!(chunked[(5, 5)], '.');
+
+        chunked[(-1, -1)] = '#';
+        assert_eq!(chunked.to_string(), "#..\n.#.\n.#.\n");
+
+        let chunked = ChunkedGrid::from(&Grid::from([[1, 2], [3, 4]]));
+        assert_eq!(chunked[(2, 0)], 0);
+        assert_eq!(chunked[(1, 1)], 4);
+
+        let empty = ChunkedGrid::from(&Grid::<u8>::from_flat(Vec::new(), 0));
+        assert_eq!(empty.bounds(), None);
+        assert_eq!(empty.num_chunks(), 0);
+    }
+
+    #[test]
+    fn mutable_reads_and_equality() {
+        let mut grid = ChunkedGrid::new('.');
+        assert_eq!(*grid.get_mut((100, 100)), '.');
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.num_chunks(), 1);
+        assert_eq!(grid, ChunkedGrid::new('.'));
+        assert_ne!(grid, ChunkedGrid::new('#'));
+
+        grid[(1, -1)] = '#';
+        assert_eq!(grid.bounds(), Some(Box2D::new((1, -1), (1, -1))));
+        let mut other = ChunkedGrid::new('.');
+        other.set((1, -1), '#');
+        assert_eq!(grid, other);
+
+        grid[(1, -1)] = '.';
+        assert_eq!(grid.bounds(), None);
+        assert_ne!(grid, other);
+    }
+}
@@ -0,0 +1,173 @@
+//! Cycle detection for iterated functions `x, f(x), f(f(x)), ...`, e.g. to fast-forward a
+//! simulation to step 10^12. All functions loop forever if the sequence never repeats.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The states from index `start` on repeat with period `length`, where the state at index 0 is
+/// the initial state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The smallest index whose state equals the state at index `n`
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Stores the index of every state in a [`HashMap`], so `f` is called only `start + length`
+/// times
+pub fn find_cycle<S, F>(initial: S, f: F) -> Cycle
+where
+    S: Clone + Hash + Eq,
+    F: Fn(&S) -> S,
+{
+    let mut seen = HashMap::new();
+    let mut state = initial;
+    for i in 0.. {
+        if let Some(start) = seen.insert(state.clone(), i) {
+            return Cycle { start, length: i - start };
+        }
+        state = f(&state);
+    }
+    unreachable!()
+}
+
+/// Floyd's tortoise and hare, needs constant memory but calls `f` several times per state
+pub fn find_cycle_floyd<S, F>(initial: S, f: F) -> Cycle
+where
+    S: Clone + Eq,
+    F: Fn(&S) -> S,
+{
+    let mut tortoise = f(&initial);
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&f(&hare));
+    }
+
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = f(&tortoise);
+    while tortoise != hare {
+        hare = f(&hare);
+        length += 1;
+    }
+    Cycle { start, length }
+}
+
+/// Brent's algorithm, needs constant memory and usually fewer calls to `f` than
+/// [`find_cycle_floyd`]
+pub fn find_cycle_brent<S, F>(initial: S, f: F) -> Cycle
+where
+    S: Clone + Eq,
+    F: Fn(&S) -> S,
+{
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = f(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = f(&hare);
+        length += 1;
+    }
+
+    tortoise = initial.clone();
+    hare = initial;
+    for _ in 0..length {
+        hare = f(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+    Cycle { start, length }
+}
+
+/// The state at index `n`, i.e. `f` applied `n` times to `initial`. Once a state repeats, the
+/// result is looked up from the states seen so far instead of simulating further.
+pub fn nth_state<S, F>(f: F, initial: S, n: usize) -> S
+where
+    S: Clone + Hash + Eq,
+    F: Fn(&S) -> S,
+{
+    let mut seen = HashMap::new();
+    let mut history = Vec::new();
+    let mut state = initial;
+    for i in 0..n {
+        if let Some(start) = seen.insert(state.clone(), i) {
+            let cycle = Cycle { start, length: i - start };
+            return history.swap_remove(cycle.reduce(n));
+        }
+        history.push(state.clone());
+        state = f(&state);
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0, 1, 2, 3, 4, 5, 6, 7, 3, 4, ...
+    fn rho(x: &u32) -> u32 {
+        if *x == 7 {
+            3
+        } else {
+            x + 1
+        }
+    }
+
+    #[test]
+    fn finds_cycle() {
+        let expected = Cycle { start: 3, length: 5 };
+        assert_eq!(find_cycle(0, rho), expected);
+        assert_eq!(find_cycle_floyd(0, rho), expected);
+        assert_eq!(find_cycle_brent(0, rho), expected);
+
+        let fixed_point = Cycle { start: 0, length: 1 };
+        assert_eq!(find_cycle(5, |&x| x), fixed_point);
+        assert_eq!(find_cycle_floyd(5, |&x| x), fixed_point);
+        assert_eq!(find_cycle_brent(5, |&x| x), fixed_point);
+
+        let collatz = |&x: &u64| if x % 2 == 0 { x / 2 } else { 3 * x + 1 };
+        assert_eq!(find_cycle(27, collatz), Cycle { start: 109, length: 3 });
+        assert_eq!(find_cycle_floyd(27, collatz), Cycle { start: 109, length: 3 });
+        assert_eq!(find_cycle_brent(27, collatz), Cycle { start: 109, length: 3 });
+    }
+
+    #[test]
+    fn nth() {
+        let cycle = find_cycle(0, rho);
+        assert_eq!(cycle.reduce(2), 2);
+        assert_eq!(cycle.reduce(8), 3);
+        assert_eq!(cycle.reduce(1_000_000_000_000), 5);
+
+        assert_eq!(nth_state(rho, 0, 0), 0);
+        assert_eq!(nth_state(rho, 0, 7), 7);
+        assert_eq!(nth_state(rho, 0, 8), 3);
+        assert_eq!(nth_state(rho, 0, 1_000_000_000_000), 5);
+    }
+}
@@ -2,9 +2,14 @@ use std::cmp::Reverse;
 use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::hash::Hash;
+use std::marker::PhantomData;
 
+use itertools::Either;
 use priority_queue::PriorityQueue;
 
+use crate::grid::Grid;
+use crate::math::Vec2D;
+
 pub type Cost = usize;
 
 pub trait Node: Clone + Debug + Eq + Ord + Hash {}
@@ -114,6 +119,126 @@ pub fn dijkstra<T: Node, S: Into<T>>(
     }
 }
 
+/// Unweighted graph over the cells of a grid, see [`Grid::as_graph`]
+#[derive(Debug, Clone, Copy)]
+pub struct GridGraph<'a, T, F> {
+    grid: &'a Grid<T>,
+    passable: F,
+    diagonal: bool,
+}
+
+/// Weighted graph over the cells of a grid, see [`Grid::as_weighted_graph`]
+#[derive(Debug, Clone, Copy)]
+pub struct WeightedGridGraph<'a, T, F> {
+    grid: &'a Grid<T>,
+    cost: F,
+    diagonal: bool,
+}
+
+impl<T> Grid<T> {
+    /// Graph where a cell is connected to its orthogonal neighbors if `passable(from, to)` holds
+    /// for their values
+    pub fn as_graph<F>(&self, passable: F) -> GridGraph<'_, T, F>
+    where
+        F: Fn(&T, &T) -> bool,
+    {
+        GridGraph { grid: self, passable, diagonal: false }
+    }
+
+    /// Graph where moving to an orthogonal neighbor costs `cost(from, to)`, `None` means the
+    /// neighbor can't be reached
+    pub fn as_weighted_graph<F>(&self, cost: F) -> WeightedGridGraph<'_, T, F>
+    where
+        F: Fn(&T, &T) -> Option<Cost>,
+    {
+        WeightedGridGraph { grid: self, cost, diagonal: false }
+    }
+}
+
+impl<T, F> GridGraph<'_, T, F> {
+    /// Also connect diagonal neighbors
+    pub fn with_diagonals(mut self) -> Self {
+        self.diagonal = true;
+        self
+    }
+}
+
+impl<T, F> WeightedGridGraph<'_, T, F> {
+    /// Also connect diagonal neighbors
+    pub fn with_diagonals(mut self) -> Self {
+        self.diagonal = true;
+        self
+    }
+}
+
+fn grid_neighbors<'a, T>(
+    grid: &'a Grid<T>,
+    pos: &'a Vec2D,
+    diagonal: bool,
+) -> impl Iterator<Item = Vec2D> + 'a {
+    if diagonal {
+        Either::Left(grid.all_neighbors(pos))
+    } else {
+        Either::Right(grid.orthogonal_neighbors(pos))
+    }
+}
+
+impl<T, F> UnweightedGraph for GridGraph<'_, T, F>
+where
+    F: Fn(&T, &T) -> bool,
+{
+    type Node = Vec2D;
+
+    fn neighbors<'a, 'b: 'a>(&'a self, node: &'b Vec2D) -> impl Iterator<Item = Vec2D> + 'a {
+        grid_neighbors(self.grid, node, self.diagonal)
+            .filter(move |n| (self.passable)(&self.grid[*node], &self.grid[*n]))
+    }
+}
+
+impl<T, F> WeightedGraph for WeightedGridGraph<'_, T, F>
+where
+    F: Fn(&T, &T) -> Option<Cost>,
+{
+    type Node = Vec2D;
+
+    fn neighbors<'a, 'b: 'a>(
+        &'a self,
+        node: &'b Vec2D,
+    ) -> impl Iterator<Item = (Vec2D, Cost)> + 'a {
+        grid_neighbors(self.grid, node, self.diagonal).filter_map(move |n| {
+            (self.cost)(&self.grid[*node], &self.grid[n]).map(|cost| (n, cost))
+        })
+    }
+}
+
+/// Weighted graph given by a closure that returns the neighbors of a node, e.g. for searches
+/// whose nodes carry more state than a grid position
+pub struct WeightedFnGraph<N, I, F> {
+    neighbors: F,
+    _marker: PhantomData<fn(&N) -> I>,
+}
+
+pub fn weighted_graph_fn<N, I, F>(neighbors: F) -> WeightedFnGraph<N, I, F>
+where
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = (N, Cost)>,
+{
+    WeightedFnGraph { neighbors, _marker: PhantomData }
+}
+
+impl<N, I, F> WeightedGraph for WeightedFnGraph<N, I, F>
+where
+    N: Node,
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = (N, Cost)>,
+{
+    type Node = N;
+
+    fn neighbors<'a, 'b: 'a>(&'a self, node: &'b N) -> impl Iterator<Item = (N, Cost)> + 'a {
+        (self.neighbors)(node).into_iter()
+    }
+}
+
 pub enum NextState<I> {
     Terminal(usize),
     Next(I),
@@ -157,8 +282,6 @@ fn dfs_impl<T>(
 mod tests {
     use std::collections::HashMap;
 
-    use crate::{grid::Grid, math::Vec2D};
-
     use super::*;
     use pretty_assertions::assert_eq;
 
@@ -197,19 +320,6 @@ mod tests {
 
     #[test]
     fn grid_diff() {
-        #[allow(non_local_definitions)]
-        impl WeightedGraph for Grid<usize> {
-            type Node = Vec2D;
-
-            fn neighbors<'a, 'b: 'a>(
-                &'a self,
-                node: &'b Self::Node,
-            ) -> impl Iterator<Item = (Self::Node, Cost)> + 'a {
-                self.orthogonal_neighbors(node)
-                    .map(|neighbor| (neighbor, self[*node].abs_diff(self[neighbor])))
-            }
-        }
-
         let grid: Grid<usize> = vec![
             vec![0, 2, 9, 3, 1, 2],
             vec![1, 9, 1, 3, 3, 3],
@@ -218,25 +328,25 @@ mod tests {
         ]
         .into();
 
-        assert_eq!(dijkstra(&grid, [Vec2D::new(0, 0)], |&node| node == Vec2D::new(5, 3)), Some(11));
-        assert_eq!(dijkstra(&grid, [Vec2D::new(0, 0)], |&node| node == Vec2D::new(-1, -1)), None);
-        assert_eq!(dijkstra(&grid, [Vec2D::new(0, 0)], |&node| node == Vec2D::new(0, 0)), Some(0));
+        let graph = grid.as_weighted_graph(|from, to| Some(from.abs_diff(*to)));
+
+        assert_eq!(
+            dijkstra(&graph, [Vec2D::new(0, 0)], |&node| node == Vec2D::new(5, 3)),
+            Some(11)
+        );
+        assert_eq!(dijkstra(&graph, [Vec2D::new(0, 0)], |&node| node == Vec2D::new(-1, -1)), None);
+        assert_eq!(dijkstra(&graph, [Vec2D::new(0, 0)], |&node| node == Vec2D::new(0, 0)), Some(0));
+
+        let walls = grid.as_weighted_graph(|_, &to| (to != 9).then_some(1));
+        assert_eq!(
+            dijkstra(&walls, [Vec2D::new(0, 0)], |&node| node == Vec2D::new(5, 3)),
+            Some(10)
+        );
+        assert_eq!(dijkstra(&walls, [Vec2D::new(0, 0)], |&node| node == Vec2D::new(2, 1)), Some(5));
     }
 
     #[test]
     fn grid_bfs() {
-        #[allow(non_local_definitions)]
-        impl UnweightedGraph for Grid<char> {
-            type Node = Vec2D;
-
-            fn neighbors<'a, 'b: 'a>(
-                &'a self,
-                node: &'b Vec2D,
-            ) -> impl Iterator<Item = Vec2D> + 'a {
-                self.all_neighbors(node).filter(move |pos| self[*pos] != '#')
-            }
-        }
-
         let grid: Grid<char> = vec![
             vec!['.', '#', '#', '.', '.', '.', '.'],
             vec!['.', '.', '#', '.', '#', '#', '.'],
@@ -244,13 +354,16 @@ mod tests {
             vec!['.', '.', '.', '#', '.', '#', '.'],
         ]
         .into();
+        let graph = grid.as_graph(|_, &to| to != '#').with_diagonals();
 
-        assert_eq!(bfs(&grid, (0, 0), (6, 3)).distance, Some(11));
-        assert_eq!(bfs(&grid, (0, 0), (2, 2)).distance, None);
-        assert_eq!(bfs(&grid, (0, 0), (0, 0)).distance, Some(0));
+        assert_eq!(bfs(&graph, (0, 0), (6, 3)).distance, Some(11));
+        assert_eq!(bfs(&graph, (0, 0), (2, 2)).distance, None);
+        assert_eq!(bfs(&graph, (0, 0), (0, 0)).distance, Some(0));
+        assert_eq!(bfs(&grid.as_graph(|_, &to| to != '#'), (0, 0), (6, 3)).distance, None);
+        assert_eq!(floodfill(&grid.as_graph(|_, &to| to != '#'), (0, 0)).len(), 7);
 
         assert_eq!(
-            bfs(&grid, (0, 0), (2, 3)),
+            bfs(&graph, (0, 0), (2, 3)),
             BfsResult {
                 distance: Some(4),
                 visited: [
@@ -307,4 +420,12 @@ mod tests {
 
         assert_eq!(dfs(&StateGraph {}, State::A), Some(13)); // A -> C -> H -> 13
     }
+
+    #[test]
+    fn fn_graph() {
+        // Counting up by 1 or 2 steps, where big steps are expensive
+        let graph = weighted_graph_fn(|&n: &u32| [(n + 1, 3), (n + 2, 5)]);
+        assert_eq!(dijkstra(&graph, [0u32], |&n| n == 4), Some(10));
+        assert_eq!(dijkstra(&graph, [0u32], |&n| n == 5), Some(13));
+    }
 }
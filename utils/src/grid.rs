@@ -4,9 +4,49 @@ use itertools::Itertools;
 
 use crate::math::Vec2D;
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+mod transform;
+pub use transform::*;
+mod view;
+pub use view::*;
+mod pattern;
+pub use pattern::*;
+mod topology;
+pub use topology::*;
+mod regions;
+pub use regions::*;
+mod ray;
+mod render;
+pub use render::*;
+mod bit_grid;
+pub use bit_grid::*;
+
+/// Stored as a single row-major `Vec`, so that the whole grid is one contiguous allocation
+#[derive(Debug, Clone)]
 pub struct Grid<T> {
-    data: Vec<Vec<T>>,
+    data: Vec<T>,
+    num_cols: usize,
+    num_rows: usize,
+    topology: Topology,
+}
+
+/// Only the cells are compared, the [`Topology`] is not, so that e.g. cycle detection over grid
+/// states doesn't depend on how the grid is walked
+impl<T: PartialEq> PartialEq for Grid<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.num_cols == other.num_cols
+            && self.num_rows == other.num_rows
+            && self.data == other.data
+    }
+}
+
+impl<T: Eq> Eq for Grid<T> {}
+
+impl<T: std::hash::Hash> std::hash::Hash for Grid<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.data.hash(state);
+        self.num_cols.hash(state);
+        self.num_rows.hash(state);
+    }
 }
 
 impl<T, InnerIter> FromIterator<InnerIter> for Grid<T>
@@ -22,11 +62,21 @@ impl<T, InnerIter: IntoIterator<Item = T>, Iter: IntoIterator<Item = InnerIter>>
     for Grid<T>
 {
     fn from(iter: Iter) -> Self {
-        let grid = Grid {
-            data: iter.into_iter().map(|iter| iter.into_iter().collect_vec()).collect_vec(),
-        };
-        assert!(grid.data.iter().all(|row| row.len() == grid.data[0].len()));
-        grid
+        let mut data = Vec::new();
+        let mut num_cols = None;
+        let mut num_rows = 0;
+        for row in iter {
+            let len_before = data.len();
+            data.extend(row);
+            let row_len = data.len() - len_before;
+            assert_eq!(
+                *num_cols.get_or_insert(row_len),
+                row_len,
+                "All rows must have equal length"
+            );
+            num_rows += 1;
+        }
+        Grid { data, num_cols: num_cols.unwrap_or(0), num_rows, topology: Topology::default() }
     }
 }
 
@@ -68,48 +118,105 @@ where
 
 impl<T> Grid<T> {
     pub fn new(data: Vec<Vec<T>>) -> Self {
-        assert!(data.iter().all(|row| row.len() == data[0].len()));
-        Grid { data }
+        data.into()
+    }
+
+    /// Creates a grid from row-major data
+    pub fn from_flat(data: Vec<T>, num_cols: usize) -> Self {
+        let num_rows = data.len().checked_div(num_cols).unwrap_or(0);
+        assert_eq!(num_rows * num_cols, data.len(), "Data does not fit into {num_cols} columns");
+        Grid { data, num_cols, num_rows, topology: Topology::default() }
     }
 
-    pub fn inner(&self) -> &Vec<Vec<T>> {
+    /// Only kept for compatibility with the old nested storage, prefer [`Grid::row_slice`] or
+    /// [`Grid::as_slice`]
+    pub fn inner(&self) -> Vec<&[T]> {
+        (0..self.num_rows).map(|row| self.row_slice(row)).collect()
+    }
+
+    /// Only kept for compatibility with the old nested storage, prefer [`Grid::row_slice_mut`] or
+    /// [`Grid::as_mut_slice`]
+    pub fn inner_mut(&mut self) -> Vec<&mut [T]> {
+        let mut rest = self.data.as_mut_slice();
+        (0..self.num_rows)
+            .map(|_| {
+                let (row, tail) = std::mem::take(&mut rest).split_at_mut(self.num_cols);
+                rest = tail;
+                row
+            })
+            .collect()
+    }
+
+    /// All cells in row-major order
+    pub fn as_slice(&self) -> &[T] {
         &self.data
     }
 
-    pub fn inner_mut(&mut self) -> &mut Vec<Vec<T>> {
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
         &mut self.data
     }
 
-    pub fn get_wrapping(&self, pos: impl Into<Vec2D>) -> &T {
-        let mut pos = pos.into();
+    pub fn into_flat(self) -> Vec<T> {
+        self.data
+    }
 
-        while pos.x < 0 {
-            pos.x += self.num_cols() as i64;
-        }
+    pub fn row_slice(&self, row: impl UnwrapIntoUsize) -> &[T] {
+        let start = row.unwrap_usize() * self.num_cols;
+        &self.data[start..start + self.num_cols]
+    }
 
-        while pos.y < 0 {
-            pos.y += self.num_rows() as i64;
-        }
+    pub fn row_slice_mut(&mut self, row: impl UnwrapIntoUsize) -> &mut [T] {
+        let start = row.unwrap_usize() * self.num_cols;
+        &mut self.data[start..start + self.num_cols]
+    }
 
-        &self[(pos.x % self.num_cols() as i64, pos.y % self.num_cols() as i64)]
+    fn flat_index(&self, pos: &Vec2D) -> usize {
+        pos.y as usize * self.num_cols + pos.x as usize
     }
 
-    pub fn get(&self, pos: impl Into<Vec2D>) -> Option<&T> {
+    fn pos_from_flat_index(&self, index: usize) -> Vec2D {
+        (index % self.num_cols, index / self.num_cols).into()
+    }
+
+    /// Reads the grid as if it was [`Topology::Toroidal`], regardless of its actual topology
+    pub fn get_wrapping(&self, pos: impl Into<Vec2D>) -> &T {
+        let pos = Topology::Toroidal.storage_pos(pos.into(), self.num_cols(), self.num_rows());
+        &self.data[self.flat_index(&pos.unwrap())]
+    }
+
+    /// Reads the grid as if it was [`Topology::Bounded`], regardless of its actual topology
+    pub fn get_bounded(&self, pos: impl Into<Vec2D>) -> Option<&T> {
         let pos = pos.into();
-        self.contains(&pos).then(|| &self.data[pos.y as usize][pos.x as usize])
+        self.contains(&pos).then(|| &self.data[self.flat_index(&pos)])
+    }
+
+    /// Respects the grid's [`Topology`], i.e. positions outside the bounds are only `None` for
+    /// bounded grids
+    pub fn get(&self, pos: impl Into<Vec2D>) -> Option<&T> {
+        let pos = self.storage_pos(pos.into())?;
+        Some(&self.data[self.flat_index(&pos)])
     }
 
     pub fn get_mut(&mut self, pos: impl Into<Vec2D>) -> Option<&mut T> {
-        let pos = pos.into();
-        self.contains(&pos).then(|| &mut self.data[pos.y as usize][pos.x as usize])
+        let pos = self.storage_pos(pos.into())?;
+        let index = self.flat_index(&pos);
+        Some(&mut self.data[index])
+    }
+
+    /// Panics like indexing does if a position can't be resolved
+    pub fn swap(&mut self, lhs: Vec2D, rhs: Vec2D) {
+        let lhs = self.flat_index(&self.storage_pos(lhs).unwrap());
+        let rhs = self.flat_index(&self.storage_pos(rhs).unwrap());
+        self.data.swap(lhs, rhs);
     }
 
+    /// Whether `pos` is inside the grid's bounds, regardless of its [`Topology`]
     pub fn contains(&self, pos: &Vec2D) -> bool {
-        pos.x >= 0 && pos.y >= 0 && pos.x < self.num_cols() as i64 && pos.y < self.num_rows() as i64
+        pos.inside_size((self.num_cols(), self.num_rows()))
     }
 
     pub fn num_rows(&self) -> usize {
-        self.data.len()
+        self.num_rows
     }
 
     pub fn row(
@@ -117,7 +224,7 @@ impl<T> Grid<T> {
         row: impl UnwrapIntoUsize,
     ) -> impl DoubleEndedIterator<Item = (Vec2D, &T)> + '_ {
         let row = row.unwrap_usize();
-        self.data[row].iter().enumerate().map(move |(col, item)| ((col, row).into(), item))
+        self.row_slice(row).iter().enumerate().map(move |(col, item)| ((col, row).into(), item))
     }
 
     pub fn row_values(&self, row: impl UnwrapIntoUsize) -> impl Iterator<Item = &T> + '_ {
@@ -125,11 +232,11 @@ impl<T> Grid<T> {
     }
 
     pub fn rotate_row_left(&mut self, row: usize, mid: usize) {
-        self.data[row].rotate_left(mid);
+        self.row_slice_mut(row).rotate_left(mid);
     }
 
     pub fn rotate_row_right(&mut self, row: usize, mid: usize) {
-        self.data[row].rotate_right(mid);
+        self.row_slice_mut(row).rotate_right(mid);
     }
 
     pub fn rows(
@@ -139,7 +246,7 @@ impl<T> Grid<T> {
     }
 
     pub fn num_cols(&self) -> usize {
-        self.data[0].len()
+        self.num_cols
     }
 
     pub fn col(&self, col: impl UnwrapIntoUsize) -> ColIter<'_, T> {
@@ -167,25 +274,25 @@ impl<T> Grid<T> {
     }
 
     pub fn iter(&self) -> impl DoubleEndedIterator<Item = (Vec2D, &T)> + '_ {
-        self.coordinates_row_major().map(move |pos| (pos, &self[pos]))
+        self.data.iter().enumerate().map(|(index, item)| (self.pos_from_flat_index(index), item))
     }
 
     pub fn orthogonal_neighbors<'a, 'b: 'a>(
         &'a self,
         pos: &'b Vec2D,
     ) -> impl Iterator<Item = Vec2D> + 'a {
-        pos.orthogonal_neighbors().filter(move |neighbor| self.contains(neighbor))
+        pos.orthogonal_neighbors().filter_map(move |neighbor| self.resolve(neighbor))
     }
 
     pub fn diagonal_neighbors<'a, 'b: 'a>(
         &'a self,
         pos: &'b Vec2D,
     ) -> impl Iterator<Item = Vec2D> + 'a {
-        pos.diagonal_neighbors().filter(move |neighbor| self.contains(neighbor))
+        pos.diagonal_neighbors().filter_map(move |neighbor| self.resolve(neighbor))
     }
 
     pub fn all_neighbors<'a, 'b: 'a>(&'a self, pos: &'b Vec2D) -> impl Iterator<Item = Vec2D> + 'a {
-        pos.all_neighbors().filter(move |neighbor| self.contains(neighbor))
+        pos.all_neighbors().filter_map(move |neighbor| self.resolve(neighbor))
     }
 }
 
@@ -203,24 +310,24 @@ where
     T: Clone,
 {
     pub fn with_value(val: T, num_rows: usize, num_cols: usize) -> Self {
-        Grid { data: vec![vec![val; num_cols]; num_rows] }
+        Grid {
+            data: vec![val; num_rows * num_cols],
+            num_cols,
+            num_rows,
+            topology: Topology::default(),
+        }
     }
 
+    /// Keeps the grid's [`Topology`]
     pub fn pad_edges(self, with: T) -> Self {
-        let mut grid = Grid::with_value(with, self.num_rows() + 2, self.num_cols() + 2);
+        let mut grid = Grid::with_value(with, self.num_rows() + 2, self.num_cols() + 2)
+            .with_topology(self.topology);
         for (pos, item) in self.iter() {
             grid[pos + (1, 1)] = item.clone();
         }
         grid
     }
 
-    pub fn swap(&mut self, lhs: Vec2D, rhs: Vec2D) {
-        // Can't use std::mem::swap here because it would require two mutable references to self
-        let tmp = self[lhs].clone();
-        self[lhs] = self[rhs].clone();
-        self[rhs] = tmp
-    }
-
     fn rotate_col(&mut self, col: usize, mid: usize, up: bool) {
         let mut new_col = self.col(col).map(|(_, item)| item).cloned().collect_vec();
         if up {
@@ -230,7 +337,7 @@ where
         }
 
         for (row, item) in new_col.into_iter().enumerate() {
-            self.data[row][col] = item;
+            self[(col, row)] = item;
         }
     }
 
@@ -306,6 +413,12 @@ where
     }
 }
 
+/// Test grid with one cell per char. Unlike [`crate::Input::char_grid`], whitespace is kept as-is.
+#[cfg(test)]
+pub(crate) fn char_grid(s: &str) -> Grid<char> {
+    s.lines().map(str::chars).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use itertools::Itertools;
@@ -370,22 +483,22 @@ mod tests {
         let mut grid: Grid<_> = [[1, 2, 3], [4, 5, 6], [7, 8, 9]].into();
 
         grid.rotate_row_left(0, 1);
-        assert_eq!(grid.data, [[2, 3, 1], [4, 5, 6], [7, 8, 9]]);
+        assert_eq!(grid, Grid::from([[2, 3, 1], [4, 5, 6], [7, 8, 9]]));
 
         grid.rotate_row_right(0, 1);
-        assert_eq!(grid.data, [[1, 2, 3], [4, 5, 6], [7, 8, 9]]);
+        assert_eq!(grid, Grid::from([[1, 2, 3], [4, 5, 6], [7, 8, 9]]));
 
         grid.rotate_row_left(1, 2);
-        assert_eq!(grid.data, [[1, 2, 3], [6, 4, 5], [7, 8, 9]]);
+        assert_eq!(grid, Grid::from([[1, 2, 3], [6, 4, 5], [7, 8, 9]]));
 
         grid.rotate_row_right(1, 2);
-        assert_eq!(grid.data, [[1, 2, 3], [4, 5, 6], [7, 8, 9]]);
+        assert_eq!(grid, Grid::from([[1, 2, 3], [4, 5, 6], [7, 8, 9]]));
 
         grid.rotate_col_up(0, 1);
-        assert_eq!(grid.data, [[4, 2, 3], [7, 5, 6], [1, 8, 9]]);
+        assert_eq!(grid, Grid::from([[4, 2, 3], [7, 5, 6], [1, 8, 9]]));
 
         grid.rotate_col_down(0, 2);
-        assert_eq!(grid.data, [[7, 2, 3], [1, 5, 6], [4, 8, 9]]);
+        assert_eq!(grid, Grid::from([[7, 2, 3], [1, 5, 6], [4, 8, 9]]));
     }
 
     #[test]
@@ -393,13 +506,13 @@ mod tests {
         let grid: Grid<_> = [[1, 2, 3], [4, 5, 6]].into();
         let padded = grid.pad_edges(0);
         assert_eq!(
-            padded.data,
-            vec![
+            padded,
+            Grid::from(vec![
                 vec![0, 0, 0, 0, 0],
                 vec![0, 1, 2, 3, 0],
                 vec![0, 4, 5, 6, 0],
                 vec![0, 0, 0, 0, 0],
-            ]
+            ])
         );
     }
 
@@ -412,6 +525,39 @@ mod tests {
         assert_eq!(grid.get_wrapping((-6, -2)), &1);
         assert_eq!(grid.get_wrapping((-1, 1)), &6);
         assert_eq!(grid.get_wrapping((-1, -1)), &6);
+        assert_eq!(grid.get_wrapping((0, 2)), &1);
+        assert_eq!(grid.get_wrapping((4, 3)), &5);
+    }
+
+    #[test]
+    fn flat_storage() {
+        let mut grid: Grid<_> = [[1, 2, 3], [4, 5, 6]].into();
+        assert_eq!(grid.as_slice(), [1, 2, 3, 4, 5, 6]);
+        assert_eq!(grid.row_slice(1), [4, 5, 6]);
+        assert_eq!(grid.inner(), vec![[1, 2, 3], [4, 5, 6]]);
+
+        grid.row_slice_mut(0)[1] = 7;
+        grid.inner_mut()[1][2] = 8;
+        assert_eq!(grid, Grid::from_flat(vec![1, 7, 3, 4, 5, 8], 3));
+        assert_eq!(grid.clone().into_flat(), [1, 7, 3, 4, 5, 8]);
+
+        grid.swap((0, 0).into(), (2, 1).into());
+        assert_eq!(grid.as_slice(), [8, 7, 3, 4, 5, 1]);
+
+        let mut no_cols: Grid<i32> = Grid::from(vec![vec![], vec![]]);
+        assert_eq!(no_cols.inner().len(), 2);
+        assert_eq!(no_cols.inner_mut().len(), 2);
+
+        let empty: Grid<i32> = Grid::from(Vec::<Vec<i32>>::new());
+        assert_eq!((empty.num_rows(), empty.num_cols()), (0, 0));
+        assert_eq!(empty.iter().count(), 0);
+        assert_eq!(empty.get((0, 0)), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn unequal_row_lengths() {
+        let _ = Grid::from([vec![1, 2], vec![3]]);
     }
 
     #[test]
@@ -0,0 +1,376 @@
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Index};
+
+use crate::math::Vec2D;
+
+use super::Grid;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Grid of booleans packed into one bit per cell. Every row starts at a new word, so row-wise
+/// operations don't need to deal with bits of neighboring rows.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    words: Vec<u64>,
+    num_cols: usize,
+    num_rows: usize,
+    words_per_row: usize,
+}
+
+impl BitGrid {
+    /// All cells unset
+    pub fn new(num_cols: usize, num_rows: usize) -> Self {
+        let words_per_row = num_cols.div_ceil(WORD_BITS);
+        Self { words: vec![0; words_per_row * num_rows], num_cols, num_rows, words_per_row }
+    }
+
+    pub fn num_cols(&self) -> usize {
+        self.num_cols
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    pub fn contains(&self, pos: &Vec2D) -> bool {
+        pos.inside_size((self.num_cols, self.num_rows))
+    }
+
+    fn word_and_mask(&self, pos: &Vec2D) -> (usize, u64) {
+        let (x, y) = (pos.x as usize, pos.y as usize);
+        (y * self.words_per_row + x / WORD_BITS, 1 << (x % WORD_BITS))
+    }
+
+    /// `None` if `pos` is outside the grid
+    pub fn get(&self, pos: impl Into<Vec2D>) -> Option<bool> {
+        let pos = pos.into();
+        self.contains(&pos).then(|| {
+            let (word, mask) = self.word_and_mask(&pos);
+            self.words[word] & mask != 0
+        })
+    }
+
+    /// Like [`BitGrid::get`], but positions outside the grid are never set
+    pub fn is_set(&self, pos: impl Into<Vec2D>) -> bool {
+        self.get(pos).unwrap_or(false)
+    }
+
+    /// Panics if `pos` is outside the grid
+    pub fn set(&mut self, pos: impl Into<Vec2D>, value: bool) {
+        let pos = pos.into();
+        assert!(self.contains(&pos), "{pos} is outside the grid");
+        let (word, mask) = self.word_and_mask(&pos);
+        if value {
+            self.words[word] |= mask;
+        } else {
+            self.words[word] &= !mask;
+        }
+    }
+
+    /// Sets the cell and returns whether it was unset before, like
+    /// [`std::collections::HashSet::insert`], so the grid can be used as a visited set
+    pub fn insert(&mut self, pos: impl Into<Vec2D>) -> bool {
+        let pos = pos.into();
+        let was_set = self.is_set(pos);
+        self.set(pos, true);
+        !was_set
+    }
+
+    /// Unsets the cell and returns whether it was set before
+    pub fn remove(&mut self, pos: impl Into<Vec2D>) -> bool {
+        let pos = pos.into();
+        let was_set = self.is_set(pos);
+        self.set(pos, false);
+        was_set
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Number of set cells
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2D, bool)> + '_ {
+        (0..self.num_rows).flat_map(move |y| {
+            (0..self.num_cols).map(move |x| {
+                let pos = Vec2D::from((x, y));
+                (pos, self.is_set(pos))
+            })
+        })
+    }
+
+    /// Positions of all set cells in row-major order
+    pub fn ones(&self) -> impl Iterator<Item = Vec2D> + '_ {
+        self.iter().filter_map(|(pos, set)| set.then_some(pos))
+    }
+
+    fn row_words(&self, row: usize) -> &[u64] {
+        &self.words[row * self.words_per_row..(row + 1) * self.words_per_row]
+    }
+
+    /// Clears the bits past the last column, which shifts and `!` would otherwise leave behind
+    fn clear_padding(&mut self) {
+        let used_bits = self.num_cols % WORD_BITS;
+        if used_bits == 0 {
+            return;
+        }
+        let mask = (1 << used_bits) - 1;
+        for row in 0..self.num_rows {
+            self.words[(row + 1) * self.words_per_row - 1] &= mask;
+        }
+    }
+
+    /// Moves every cell `n` columns to the right, cells that leave the grid are dropped
+    pub fn shift_right(&mut self, n: usize) {
+        let (word_shift, bit_shift) = (n / WORD_BITS, n % WORD_BITS);
+        for row in 0..self.num_rows {
+            let old = self.row_words(row).to_vec();
+            let word = |i: usize| i.checked_sub(word_shift).and_then(|i| old.get(i)).copied();
+            for i in 0..self.words_per_row {
+                let carry = match (bit_shift, i.checked_sub(1).and_then(word)) {
+                    (0, _) | (_, None) => 0,
+                    (_, Some(lower)) => lower >> (WORD_BITS - bit_shift),
+                };
+                self.words[row * self.words_per_row + i] =
+                    (word(i).unwrap_or(0) << bit_shift) | carry;
+            }
+        }
+        self.clear_padding();
+    }
+
+    /// Moves every cell `n` columns to the left, cells that leave the grid are dropped
+    pub fn shift_left(&mut self, n: usize) {
+        let (word_shift, bit_shift) = (n / WORD_BITS, n % WORD_BITS);
+        for row in 0..self.num_rows {
+            let old = self.row_words(row).to_vec();
+            let word = |i: usize| old.get(i + word_shift).copied().unwrap_or(0);
+            for i in 0..self.words_per_row {
+                let carry = if bit_shift == 0 { 0 } else { word(i + 1) << (WORD_BITS - bit_shift) };
+                self.words[row * self.words_per_row + i] = (word(i) >> bit_shift) | carry;
+            }
+        }
+    }
+
+    /// Moves every row `n` rows down, rows that leave the grid are dropped
+    pub fn shift_down(&mut self, n: usize) {
+        let n = n.min(self.num_rows) * self.words_per_row;
+        self.words.rotate_right(n);
+        self.words[..n].fill(0);
+    }
+
+    /// Moves every row `n` rows up, rows that leave the grid are dropped
+    pub fn shift_up(&mut self, n: usize) {
+        let n = n.min(self.num_rows) * self.words_per_row;
+        self.words.rotate_left(n);
+        let len = self.words.len();
+        self.words[len - n..].fill(0);
+    }
+
+    pub fn to_grid(&self) -> Grid<bool> {
+        Grid::from_flat(self.iter().map(|(_, set)| set).collect(), self.num_cols)
+    }
+
+    pub fn to_char_grid(&self, set: char, unset: char) -> Grid<char> {
+        Grid::from_flat(
+            self.iter().map(|(_, is_set)| if is_set { set } else { unset }).collect(),
+            self.num_cols,
+        )
+    }
+
+    fn assert_same_size(&self, other: &BitGrid) {
+        assert_eq!(
+            (self.num_cols, self.num_rows),
+            (other.num_cols, other.num_rows),
+            "Grids must have the same size"
+        );
+    }
+}
+
+impl<T> Grid<T> {
+    /// Sets every cell for which `predicate` holds, e.g. `grid.to_bit_grid(|&c| c == '#')`
+    pub fn to_bit_grid(&self, predicate: impl Fn(&T) -> bool) -> BitGrid {
+        let mut bits = BitGrid::new(self.num_cols(), self.num_rows());
+        for (pos, item) in self.iter() {
+            if predicate(item) {
+                bits.set(pos, true);
+            }
+        }
+        bits
+    }
+}
+
+impl From<&Grid<bool>> for BitGrid {
+    fn from(grid: &Grid<bool>) -> Self {
+        grid.to_bit_grid(|&set| set)
+    }
+}
+
+impl<Pos> Index<Pos> for BitGrid
+where
+    Pos: Into<Vec2D>,
+{
+    type Output = bool;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        let pos = pos.into();
+        match self.get(pos) {
+            Some(true) => &true,
+            Some(false) => &false,
+            None => panic!("{pos} is outside the grid"),
+        }
+    }
+}
+
+macro_rules! impl_bit_op {
+    ($op:ident, $op_fn:ident, $assign:ident, $assign_fn:ident) => {
+        impl $assign<&BitGrid> for BitGrid {
+            fn $assign_fn(&mut self, rhs: &BitGrid) {
+                self.assert_same_size(rhs);
+                for (lhs, rhs) in self.words.iter_mut().zip(&rhs.words) {
+                    lhs.$assign_fn(rhs);
+                }
+            }
+        }
+
+        impl $op<&BitGrid> for &BitGrid {
+            type Output = BitGrid;
+
+            fn $op_fn(self, rhs: &BitGrid) -> BitGrid {
+                let mut result = self.clone();
+                result.$assign_fn(rhs);
+                result
+            }
+        }
+    };
+}
+
+impl_bit_op!(BitAnd, bitand, BitAndAssign, bitand_assign);
+impl_bit_op!(BitOr, bitor, BitOrAssign, bitor_assign);
+impl_bit_op!(BitXor, bitxor, BitXorAssign, bitxor_assign);
+
+impl std::ops::Not for &BitGrid {
+    type Output = BitGrid;
+
+    fn not(self) -> BitGrid {
+        let mut result = self.clone();
+        result.words.iter_mut().for_each(|word| *word = !*word);
+        result.clear_padding();
+        result
+    }
+}
+
+/// Set cells are drawn as `#`, unset ones as `.`
+impl std::fmt::Display for BitGrid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_char_grid('#', '.'))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use itertools::Itertools;
+
+    use crate::grid::char_grid;
+
+    use super::*;
+
+    fn bits(s: &str) -> BitGrid {
+        char_grid(s).to_bit_grid(|&c| c == '#')
+    }
+
+    #[test]
+    fn get_and_set() {
+        let mut grid = BitGrid::new(70, 2);
+        assert_eq!(grid.get((69, 1)), Some(false));
+        assert_eq!(grid.get((70, 1)), None);
+        assert!(!grid.is_set((-1, 0)));
+
+        grid.set((65, 1), true);
+        assert!(grid[(65, 1)]);
+        assert!(!grid.insert((65, 1)));
+        assert!(grid.insert((3, 0)));
+        assert_eq!(grid.count_ones(), 2);
+        assert_eq!(grid.ones().collect_vec(), [(3, 0), (65, 1)]);
+
+        assert!(grid.remove((3, 0)));
+        assert!(!grid.remove((3, 0)));
+        assert_eq!(grid.count_ones(), 1);
+        grid.clear();
+        assert_eq!(grid.count_ones(), 0);
+    }
+
+    #[test]
+    fn conversions() {
+        let chars = char_grid("#.#\n.##");
+        let grid = chars.to_bit_grid(|&c| c == '#');
+        assert_eq!(grid.to_char_grid('#', '.'), chars);
+        assert_eq!(grid.to_string(), "#.#\n.##\n");
+        assert_eq!(BitGrid::from(&grid.to_grid()), grid);
+        assert_eq!(grid.to_grid(), Grid::from([[true, false, true], [false, true, true]]));
+    }
+
+    #[test]
+    fn bit_ops() {
+        let (a, b) = (bits("##..\n#.#."), bits("#.#.\n##.."));
+        assert_eq!(&a & &b, bits("#...\n#..."));
+        assert_eq!(&a | &b, bits("###.\n###."));
+        assert_eq!(&a ^ &b, bits(".##.\n.##."));
+        assert_eq!(!&a, bits("..##\n.#.#"));
+        assert_eq!((!&a).count_ones(), 4);
+
+        let mut c = a.clone();
+        c ^= &a;
+        assert_eq!(c.count_ones(), 0);
+    }
+
+    #[test]
+    fn shifts() {
+        let mut grid = bits("#..#\n.##.");
+        grid.shift_right(1);
+        assert_eq!(grid, bits(".#..\n..##"));
+        grid.shift_left(2);
+        assert_eq!(grid, bits("....\n##.."));
+        grid.shift_up(1);
+        assert_eq!(grid, bits("##..\n...."));
+        grid.shift_down(5);
+        assert_eq!(grid.count_ones(), 0);
+
+        // Across word boundaries
+        let mut wide = BitGrid::new(150, 2);
+        wide.set((0, 0), true);
+        wide.set((63, 1), true);
+        wide.shift_right(70);
+        assert_eq!(wide.ones().collect_vec(), [(70, 0), (133, 1)]);
+        wide.shift_right(20);
+        assert_eq!(wide.ones().collect_vec(), [(90, 0)]);
+        wide.shift_left(27);
+        assert_eq!(wide.ones().collect_vec(), [(63, 0)]);
+        wide.shift_down(1);
+        assert_eq!(wide.ones().collect_vec(), [(63, 1)]);
+    }
+
+    #[test]
+    fn bfs_visited() {
+        let walls = bits(".#...\n.#.#.\n...#.");
+        let mut visited = BitGrid::new(walls.num_cols(), walls.num_rows());
+        let mut queue = VecDeque::from([(Vec2D::new(0, 0), 0)]);
+        visited.insert((0, 0));
+        let mut distance_to_end = None;
+        while let Some((pos, distance)) = queue.pop_front() {
+            if pos == (4, 2) {
+                distance_to_end = Some(distance);
+            }
+            for n in pos.orthogonal_neighbors() {
+                if walls.get(n) == Some(false) && visited.insert(n) {
+                    queue.push_back((n, distance + 1));
+                }
+            }
+        }
+        assert_eq!(distance_to_end, Some(10));
+        assert_eq!(visited.count_ones(), 11);
+    }
+}
@@ -0,0 +1,132 @@
+use itertools::Itertools;
+
+use crate::math::Vec2D;
+
+use super::{Grid, GridView, Symmetry};
+
+/// A 2D pattern where `None` matches any cell
+pub type Template<T> = Grid<Option<T>>;
+
+impl<T> Grid<T>
+where
+    T: PartialEq + Clone,
+{
+    /// Turns this grid into a [`Template`], where every cell equal to `wildcard` matches anything
+    pub fn to_template(&self, wildcard: &T) -> Template<T> {
+        Grid::from_flat(
+            self.as_slice().iter().map(|item| (item != wildcard).then(|| item.clone())).collect(),
+            self.num_cols(),
+        )
+    }
+
+    /// Finds all occurrences of `template`, returning the upper left corner of each match
+    pub fn find_template<'a>(
+        &'a self,
+        template: &'a Template<T>,
+    ) -> impl Iterator<Item = Vec2D> + 'a {
+        self.windows(template.num_cols(), template.num_rows())
+            .filter(|window| window_matches(window, template))
+            .map(|window| window.offset())
+    }
+
+    /// Like [`Grid::find_template`], but also tries all rotations and reflections of the
+    /// template. Symmetries that map the template onto itself are only tried once, so every
+    /// match is reported exactly once.
+    pub fn find_template_any_orientation(&self, template: &Template<T>) -> Vec<(Vec2D, Symmetry)> {
+        let mut variants: Vec<(Template<T>, Symmetry)> = Vec::new();
+        for symmetry in Symmetry::ALL {
+            let variant = template.transform(symmetry);
+            if !variants.iter().any(|(other, _)| *other == variant) {
+                variants.push((variant, symmetry));
+            }
+        }
+
+        variants
+            .iter()
+            .flat_map(|(variant, symmetry)| {
+                self.find_template(variant).map(move |pos| (pos, *symmetry))
+            })
+            .collect()
+    }
+}
+
+fn window_matches<T: PartialEq>(window: &GridView<'_, T>, template: &Template<T>) -> bool {
+    template.iter().all(|(pos, expected)| expected.as_ref().is_none_or(|e| *e == window[pos]))
+}
+
+impl<T> Grid<T>
+where
+    T: PartialEq,
+{
+    /// Whether `sequence` can be read starting at `start` in steps of `direction`. Like rays,
+    /// sequences never wrap around, regardless of the grid's [`super::Topology`].
+    pub fn matches_sequence(&self, start: Vec2D, direction: Vec2D, sequence: &[T]) -> bool {
+        sequence
+            .iter()
+            .enumerate()
+            .all(|(i, expected)| self.get_bounded(start + direction * i) == Some(expected))
+    }
+
+    /// Finds all occurrences of `sequence` along any of the given directions, e.g. for a word
+    /// search. Returns the start position and direction of each match.
+    pub fn find_sequence<'a>(
+        &'a self,
+        sequence: &'a [T],
+        directions: impl IntoIterator<Item = Vec2D>,
+    ) -> impl Iterator<Item = (Vec2D, Vec2D)> + 'a {
+        let directions = directions.into_iter().collect_vec();
+        self.coordinates_row_major()
+            .cartesian_product(directions)
+            .filter(|&(start, direction)| self.matches_sequence(start, direction, sequence))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::grid::{char_grid, Topology};
+
+    use super::*;
+
+    #[test]
+    fn find_sequence() {
+        let grid = char_grid("XMAS\nMM..\nA.A.\nS..S");
+        let xmas = ['X', 'M', 'A', 'S'];
+        assert_eq!(
+            grid.find_sequence(&xmas, Vec2D::zero().all_neighbors()).collect::<HashSet<_>>(),
+            HashSet::from([
+                (Vec2D::new(0, 0), Vec2D::new(1, 0)),
+                (Vec2D::new(0, 0), Vec2D::new(0, 1)),
+                (Vec2D::new(0, 0), Vec2D::new(1, 1))
+            ])
+        );
+        assert_eq!(grid.find_sequence(&xmas, [Vec2D::new(-1, 0)]).count(), 0);
+        assert!(grid.matches_sequence(Vec2D::new(3, 3), Vec2D::new(-1, -1), &['S', 'A', 'M']));
+    }
+
+    #[test]
+    fn find_template() {
+        let grid = char_grid("M.S.\n.A..\nM.SA\n..MS");
+        let template = char_grid("M?S\n?A?\nM?S").to_template(&'?');
+        assert_eq!(template[(1, 0)], None);
+        assert_eq!(template[(1, 1)], Some('A'));
+
+        assert_eq!(grid.find_template(&template).collect_vec(), [Vec2D::new(0, 0)]);
+
+        let grid = char_grid("M.M.\n.A..\nS.S.");
+        assert_eq!(grid.find_template(&template).count(), 0);
+        assert_eq!(
+            grid.find_template_any_orientation(&template),
+            [(Vec2D::new(0, 0), Symmetry::RotateCw)]
+        );
+    }
+
+    #[test]
+    fn toroidal_grid_is_not_wrapped() {
+        let grid = char_grid("ASXM\n....").with_topology(Topology::Toroidal);
+        let xmas = ['X', 'M', 'A', 'S'];
+        assert_eq!(grid.find_sequence(&xmas, Vec2D::zero().all_neighbors()).count(), 0);
+        assert!(!grid.matches_sequence(Vec2D::new(2, 0), Vec2D::new(1, 0), &xmas));
+    }
+}
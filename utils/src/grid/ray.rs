@@ -0,0 +1,132 @@
+use crate::math::Vec2D;
+
+use super::Grid;
+
+/// Rays and lines never wrap around, regardless of the grid's [`super::Topology`]
+impl<T> Grid<T> {
+    /// Walks from `start` (inclusive) in steps of `step` until leaving the grid
+    pub fn ray(
+        &self,
+        start: impl Into<Vec2D>,
+        step: impl Into<Vec2D>,
+    ) -> impl Iterator<Item = (Vec2D, &T)> + '_ {
+        let (start, step) = (start.into(), step.into());
+        assert!(!step.is_zero(), "a ray needs a non-zero step");
+        std::iter::successors(Some(start), move |&pos| Some(pos + step))
+            .map_while(|pos| self.get_bounded(pos).map(|item| (pos, item)))
+    }
+
+    /// The first cell after `start` along `step` that satisfies `predicate`
+    pub fn cast(
+        &self,
+        start: impl Into<Vec2D>,
+        step: impl Into<Vec2D>,
+        predicate: impl Fn(&T) -> bool,
+    ) -> Option<(Vec2D, &T)> {
+        self.ray(start, step).skip(1).find(|(_, item)| predicate(item))
+    }
+
+    /// The cells on the line from `from` to `to` that are inside the grid, see [`Vec2D::line_to`]
+    pub fn line(
+        &self,
+        from: impl Into<Vec2D>,
+        to: impl Into<Vec2D>,
+    ) -> impl Iterator<Item = (Vec2D, &T)> + '_ {
+        let (from, to) = (from.into(), to.into());
+        from.line_to(to).filter_map(|pos| self.get_bounded(pos).map(|item| (pos, item)))
+    }
+
+    /// Whether no cell strictly between `from` and `to` is `opaque`
+    pub fn line_of_sight(
+        &self,
+        from: impl Into<Vec2D>,
+        to: impl Into<Vec2D>,
+        opaque: impl Fn(&T) -> bool,
+    ) -> bool {
+        let (from, to) = (from.into(), to.into());
+        self.line(from, to).all(|(pos, item)| pos == from || pos == to || !opaque(item))
+    }
+
+    /// Everything that can be seen from `pos` when looking along each of the `directions`, up to
+    /// and including the first `opaque` cell
+    pub fn visible_from<'a>(
+        &'a self,
+        pos: Vec2D,
+        directions: impl IntoIterator<Item = Vec2D> + 'a,
+        opaque: impl Fn(&T) -> bool + Copy + 'a,
+    ) -> impl Iterator<Item = (Vec2D, &'a T)> + 'a {
+        directions.into_iter().flat_map(move |dir| {
+            let mut blocked = false;
+            self.ray(pos, dir).skip(1).take_while(move |(_, item)| {
+                let visible = !blocked;
+                blocked |= opaque(item);
+                visible
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use crate::grid::{char_grid, Topology};
+
+    use super::*;
+
+    #[test]
+    fn ray() {
+        let grid = char_grid("abc\ndef\nghi");
+        let values = |start: (i32, i32), step: (i32, i32)| {
+            grid.ray(start, step).map(|(_, &c)| c).collect::<String>()
+        };
+        assert_eq!(values((0, 0), (1, 1)), "aei");
+        assert_eq!(values((2, 1), (-1, 0)), "fed");
+        assert_eq!(values((0, 2), (0, -2)), "ga");
+        assert_eq!(values((3, 0), (1, 0)), "");
+        assert_eq!(grid.ray((1, 0), (0, 1)).last(), Some((Vec2D::new(1, 2), &'h')));
+    }
+
+    #[test]
+    fn cast() {
+        let grid = char_grid("..#.\n....\n#..#");
+        assert_eq!(grid.cast((0, 0), (1, 0), |&c| c == '#'), Some((Vec2D::new(2, 0), &'#')));
+        assert_eq!(grid.cast((2, 0), (1, 0), |&c| c == '#'), None);
+        assert_eq!(grid.cast((2, 0), (-1, 1), |&c| c == '#'), Some((Vec2D::new(0, 2), &'#')));
+        assert_eq!(grid.cast((0, 1), (0, 1), |&c| c == '#'), Some((Vec2D::new(0, 2), &'#')));
+    }
+
+    #[test]
+    fn line_of_sight() {
+        let grid = char_grid("a...\n.#..\n...b");
+        assert_eq!(grid.line((0, 0), (3, 2)).map(|(_, &c)| c).collect::<String>(), "a#.b");
+        assert_eq!(grid.line((-1, 0), (1, 0)).count(), 2);
+        assert!(!grid.line_of_sight((0, 0), (3, 2), |&c| c == '#'));
+        assert!(grid.line_of_sight((0, 0), (3, 0), |&c| c == '#'));
+        assert!(grid.line_of_sight((0, 0), (1, 1), |&c| c == '#'));
+    }
+
+    #[test]
+    fn visible_from() {
+        let grid = char_grid("L.L.L\n.....\nL.#.L");
+        let visible = grid
+            .visible_from(Vec2D::new(2, 0), Vec2D::zero().all_neighbors().collect_vec(), |&c| {
+                c != '.'
+            })
+            .map(|(pos, _)| pos)
+            .sorted()
+            .collect_vec();
+        assert_eq!(
+            visible,
+            [(0, 0), (0, 2), (1, 0), (1, 1), (2, 1), (2, 2), (3, 0), (3, 1), (4, 0), (4, 2)]
+        );
+    }
+
+    #[test]
+    fn toroidal_grid_is_not_wrapped() {
+        let grid = char_grid("abc\ndef").with_topology(Topology::Toroidal);
+        assert_eq!(grid.ray((1, 0), (1, 0)).map(|(_, &c)| c).collect::<String>(), "bc");
+        assert_eq!(grid.cast((2, 0), (1, 0), |_| true), None);
+        assert_eq!(grid.line((-1, 1), (1, 1)).map(|(_, &c)| c).collect::<String>(), "de");
+    }
+}
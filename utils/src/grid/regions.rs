@@ -0,0 +1,215 @@
+use std::collections::HashSet;
+
+use crate::math::{Box2D, Vec2D};
+
+use super::{Grid, Topology};
+
+/// A connected set of cells, see [`Grid::regions`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    cells: HashSet<Vec2D>,
+    /// Size of the grid if it's toroidal, so that the outline continues across its edges
+    wrap: Option<Vec2D>,
+}
+
+impl Region {
+    pub fn contains(&self, pos: &Vec2D) -> bool {
+        self.cells.contains(&self.wrap.map_or(*pos, |size| pos.rem_euclid(size)))
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = &Vec2D> + '_ {
+        self.cells.iter()
+    }
+
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Number of cell edges that border a cell outside of the region
+    pub fn perimeter(&self) -> usize {
+        self.cells
+            .iter()
+            .map(|pos| pos.orthogonal_neighbors().filter(|n| !self.contains(n)).count())
+            .sum()
+    }
+
+    /// Number of corners of the region's outline, including the outlines of holes
+    pub fn corners(&self) -> usize {
+        let diagonals = [(1, 1), (-1, 1), (1, -1), (-1, -1)];
+        self.cells
+            .iter()
+            .map(|&pos| {
+                diagonals
+                    .iter()
+                    .filter(|&&(dx, dy)| {
+                        let horizontal = self.contains(&(pos + (dx, 0)));
+                        let vertical = self.contains(&(pos + (0, dy)));
+                        let diagonal = self.contains(&(pos + (dx, dy)));
+                        // Convex corner || concave corner
+                        (!horizontal && !vertical) || (horizontal && vertical && !diagonal)
+                    })
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Number of straight sides of the region's outline. Since every side starts at a corner,
+    /// this is the same as [`Region::corners`].
+    pub fn sides(&self) -> usize {
+        self.corners()
+    }
+
+    pub fn bounding_box(&self) -> Box2D {
+        self.cells.iter().copied().collect()
+    }
+
+    /// Cells that have at least one orthogonal neighbor outside of the region
+    pub fn boundary(&self) -> impl Iterator<Item = &Vec2D> + '_ {
+        self.cells.iter().filter(|pos| pos.orthogonal_neighbors().any(|n| !self.contains(&n)))
+    }
+}
+
+/// All regions of a grid, see [`Grid::regions`]
+#[derive(Debug, Clone, derive_more::Deref)]
+pub struct Regions {
+    #[deref]
+    regions: Vec<Region>,
+    labels: Grid<usize>,
+}
+
+impl Regions {
+    /// Index of the region that contains `pos`
+    pub fn label(&self, pos: impl Into<Vec2D>) -> usize {
+        self.labels[pos]
+    }
+
+    pub fn region_at(&self, pos: impl Into<Vec2D>) -> &Region {
+        &self.regions[self.label(pos)]
+    }
+
+    /// Grid with the index of each cell's region
+    pub fn labels(&self) -> &Grid<usize> {
+        &self.labels
+    }
+}
+
+impl<T> Grid<T> {
+    /// Splits the grid into orthogonally connected regions in a single pass. Two neighboring cells
+    /// belong to the same region if `connected` returns true for their values. On a toroidal grid,
+    /// regions connect across the edges. Panics for [`Topology::Tiled`] grids, whose regions
+    /// can be infinite.
+    pub fn regions(&self, connected: impl Fn(&T, &T) -> bool) -> Regions {
+        assert_ne!(self.topology(), Topology::Tiled, "Regions of tiled grids are not supported");
+        let wrap = (self.topology() == Topology::Toroidal)
+            .then(|| Vec2D::from((self.num_cols(), self.num_rows())));
+        let mut labels: Grid<Option<usize>> =
+            Grid::from_flat(self.as_slice().iter().map(|_| None).collect(), self.num_cols());
+        let mut regions = Vec::new();
+
+        for start in self.coordinates_row_major() {
+            if labels[start].is_some() {
+                continue;
+            }
+
+            let label = regions.len();
+            labels[start] = Some(label);
+            let mut cells = HashSet::from([start]);
+            let mut stack = vec![start];
+
+            while let Some(pos) = stack.pop() {
+                for neighbor in self.orthogonal_neighbors(&pos) {
+                    if labels[neighbor].is_none() && connected(&self[pos], &self[neighbor]) {
+                        labels[neighbor] = Some(label);
+                        cells.insert(neighbor);
+                        stack.push(neighbor);
+                    }
+                }
+            }
+
+            regions.push(Region { cells, wrap });
+        }
+
+        Regions {
+            regions,
+            labels: Grid::from_flat(
+                labels.as_slice().iter().map(|label| label.unwrap()).collect(),
+                self.num_cols(),
+            ),
+        }
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// Regions of orthogonally connected cells with equal values
+    pub fn regions_by_value(&self) -> Regions {
+        self.regions(|lhs, rhs| lhs == rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::char_grid;
+
+    use super::*;
+
+    #[test]
+    fn regions() {
+        let grid = char_grid("AAAA\nBBCD\nBBCC\nEEEC");
+        let regions = grid.regions_by_value();
+
+        assert_eq!(regions.len(), 5);
+        assert_eq!(regions.label((0, 0)), regions.label((3, 0)));
+        assert_ne!(regions.label((0, 0)), regions.label((0, 1)));
+
+        let stats = |pos: (i32, i32)| {
+            let region = regions.region_at(pos);
+            (region.area(), region.perimeter(), region.sides())
+        };
+        assert_eq!(stats((0, 0)), (4, 10, 4));
+        assert_eq!(stats((0, 1)), (4, 8, 4));
+        assert_eq!(stats((2, 1)), (4, 10, 8));
+        assert_eq!(stats((3, 1)), (1, 4, 4));
+        assert_eq!(stats((0, 3)), (3, 8, 4));
+
+        let c = regions.region_at((2, 1));
+        assert_eq!(c.bounding_box(), Box2D::new((2, 1), (3, 3)));
+        assert_eq!(c.boundary().count(), 4);
+    }
+
+    #[test]
+    fn holes() {
+        let grid = char_grid("AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA");
+        let regions = grid.regions_by_value();
+        let a = regions.region_at((0, 0));
+        assert_eq!(a.area(), 28);
+        assert_eq!(a.sides(), 12);
+        assert_eq!(a.boundary().count(), 26);
+    }
+
+    #[test]
+    fn custom_predicate() {
+        let grid: Grid<_> = [[1, 2, 9], [9, 3, 9], [5, 4, 9]].into();
+        let regions = grid.regions(|&lhs: &i32, &rhs: &i32| lhs.abs_diff(rhs) == 1);
+        assert_eq!(regions.region_at((0, 0)).area(), 5);
+        assert_eq!(regions.len(), 5);
+    }
+
+    #[test]
+    fn toroidal() {
+        let grid = char_grid("AB.\n...\nA.B").with_topology(Topology::Toroidal);
+        let regions = grid.regions_by_value();
+        assert_eq!(regions.label((0, 0)), regions.label((0, 2)));
+        assert_ne!(regions.label((1, 0)), regions.label((2, 2)));
+
+        let a = regions.region_at((0, 0));
+        assert_eq!((a.area(), a.perimeter(), a.sides()), (2, 6, 4));
+        assert!(a.contains(&Vec2D::new(0, -1)));
+        assert_eq!(regions.region_at((1, 1)).area(), 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "not supported")]
+    fn tiled() {
+        char_grid("A").with_topology(Topology::Tiled).regions_by_value();
+    }
+}
@@ -0,0 +1,198 @@
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter, Result};
+use std::io::IsTerminal;
+
+use crate::math::Vec2D;
+
+use super::Grid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl Color {
+    fn ansi_code(&self) -> u8 {
+        match self {
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+            Color::White => 37,
+        }
+    }
+
+    fn paint(&self, s: impl Display) -> String {
+        format!("\x1b[1;{}m{s}\x1b[0m", self.ansi_code())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Layer {
+    positions: HashSet<Vec2D>,
+    symbol: Option<char>,
+    color: Option<Color>,
+    label: Option<String>,
+}
+
+/// Builder for drawing positions on top of a [`Grid`], see [`Grid::render`]. Later layers are
+/// drawn over earlier ones.
+#[derive(Debug, Clone)]
+pub struct Render<'a, T> {
+    grid: &'a Grid<T>,
+    layers: Vec<Layer>,
+    ansi: bool,
+}
+
+impl<T> Grid<T> {
+    /// Uses ANSI colors if stdout is a terminal and `NO_COLOR` is not set
+    pub fn render(&self) -> Render<'_, T> {
+        let ansi = std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+        Render { grid: self, layers: Vec::new(), ansi }
+    }
+}
+
+impl<T> Render<'_, T> {
+    fn layer<P: Into<Vec2D>>(
+        mut self,
+        positions: impl IntoIterator<Item = P>,
+        symbol: Option<char>,
+        color: Option<Color>,
+    ) -> Self {
+        let positions = positions.into_iter().map(Into::into).collect();
+        self.layers.push(Layer { positions, symbol, color, label: None });
+        self
+    }
+
+    /// Colors the cells at `positions`. Has no effect on plain text output.
+    pub fn highlight<P: Into<Vec2D>>(
+        self,
+        positions: impl IntoIterator<Item = P>,
+        color: Color,
+    ) -> Self {
+        self.layer(positions, None, Some(color))
+    }
+
+    /// Draws `symbol` instead of the cells at `positions`
+    pub fn overlay<P: Into<Vec2D>>(
+        self,
+        positions: impl IntoIterator<Item = P>,
+        symbol: char,
+    ) -> Self {
+        self.layer(positions, Some(symbol), None)
+    }
+
+    pub fn overlay_colored<P: Into<Vec2D>>(
+        self,
+        positions: impl IntoIterator<Item = P>,
+        symbol: char,
+        color: Color,
+    ) -> Self {
+        self.layer(positions, Some(symbol), Some(color))
+    }
+
+    /// Describes the previously added layer in a legend below the grid
+    pub fn legend(mut self, label: impl ToString) -> Self {
+        let layer = self.layers.last_mut().expect("legend() needs a highlight or overlay first");
+        layer.label = Some(label.to_string());
+        self
+    }
+
+    /// Forces ANSI colors on or off
+    pub fn ansi(mut self, ansi: bool) -> Self {
+        self.ansi = ansi;
+        self
+    }
+
+    pub fn plain(self) -> Self {
+        self.ansi(false)
+    }
+}
+
+impl<T: Display> Display for Render<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        for row in self.grid.rows() {
+            for (pos, item) in row {
+                let symbol = self.layers.iter().rev().find_map(|layer| {
+                    layer.positions.contains(&pos).then_some(layer.symbol).flatten()
+                });
+                let color = self.layers.iter().rev().find_map(|layer| {
+                    layer.positions.contains(&pos).then_some(layer.color).flatten()
+                });
+                let cell = symbol.map_or_else(|| item.to_string(), String::from);
+                match color {
+                    Some(color) if self.ansi => write!(f, "{}", color.paint(cell))?,
+                    _ => write!(f, "{cell}")?,
+                }
+            }
+            writeln!(f)?;
+        }
+
+        for layer in &self.layers {
+            let Some(label) = &layer.label else { continue };
+            let sample = match (layer.symbol, layer.color) {
+                (Some(symbol), Some(color)) if self.ansi => color.paint(symbol),
+                (None, Some(color)) if self.ansi => color.paint('█'),
+                (Some(symbol), _) => symbol.to_string(),
+                (None, Some(color)) => format!("{color:?}").to_lowercase(),
+                (None, None) => unreachable!(),
+            };
+            writeln!(f, "{sample}: {label}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::grid::char_grid;
+
+    use super::*;
+
+    #[test]
+    fn plain() {
+        let grid = char_grid("#...\n.#..\n...#");
+        let visited = HashMap::from([(Vec2D::new(0, 1), 1), (Vec2D::new(0, 2), 2)]);
+        let render = grid
+            .render()
+            .plain()
+            .highlight([(1, 0), (2, 0)], Color::Red)
+            .legend("path")
+            .overlay(visited.keys(), 'O')
+            .legend("visited")
+            .overlay([(1, 2)], '@');
+        assert_eq!(render.to_string(), "#...\nO#..\nO@.#\nred: path\nO: visited\n");
+        assert_eq!(grid.render().plain().to_string(), grid.to_string());
+    }
+
+    #[test]
+    fn ansi() {
+        let grid: Grid<_> = [[1, 2], [3, 4]].into();
+        let render = grid
+            .render()
+            .ansi(true)
+            .highlight([(0, 0), (1, 1)], Color::Green)
+            .overlay_colored([(1, 1)], 'x', Color::Red)
+            .legend("end");
+        assert_eq!(
+            render.to_string(),
+            "\x1b[1;32m1\x1b[0m2\n3\x1b[1;31mx\x1b[0m\n\x1b[1;31mx\x1b[0m: end\n"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn legend_without_layer() {
+        let _ = char_grid("#.").render().legend("nothing");
+    }
+}
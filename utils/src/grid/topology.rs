@@ -0,0 +1,132 @@
+use crate::math::Vec2D;
+
+use super::Grid;
+
+/// How positions outside of the grid's bounds are treated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Topology {
+    /// Positions outside of the grid don't exist
+    #[default]
+    Bounded,
+    /// Leaving the grid on one side enters it again on the opposite side, so positions are
+    /// always wrapped back into the grid
+    Toroidal,
+    /// The grid repeats infinitely in all directions. Positions are *not* wrapped, but every
+    /// position reads the cell of the corresponding tile.
+    Tiled,
+}
+
+impl Topology {
+    /// The position a walker ends up at when going to `pos`, or `None` if it's not reachable
+    pub fn resolve(&self, pos: Vec2D, num_cols: usize, num_rows: usize) -> Option<Vec2D> {
+        match self {
+            Topology::Bounded => in_bounds(&pos, num_cols, num_rows).then_some(pos),
+            Topology::Toroidal => wrap(pos, num_cols, num_rows),
+            Topology::Tiled => (num_cols > 0 && num_rows > 0).then_some(pos),
+        }
+    }
+
+    /// The position inside the grid's bounds whose cell is read for `pos`
+    pub fn storage_pos(&self, pos: Vec2D, num_cols: usize, num_rows: usize) -> Option<Vec2D> {
+        match self {
+            Topology::Bounded => in_bounds(&pos, num_cols, num_rows).then_some(pos),
+            Topology::Toroidal | Topology::Tiled => wrap(pos, num_cols, num_rows),
+        }
+    }
+}
+
+fn in_bounds(pos: &Vec2D, num_cols: usize, num_rows: usize) -> bool {
+    pos.inside_size((num_cols, num_rows))
+}
+
+fn wrap(pos: Vec2D, num_cols: usize, num_rows: usize) -> Option<Vec2D> {
+    (num_cols > 0 && num_rows > 0).then(|| pos.rem_euclid((num_cols, num_rows)))
+}
+
+impl<T> Grid<T> {
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    pub fn set_topology(&mut self, topology: Topology) {
+        self.topology = topology;
+    }
+
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    /// Where `pos` ends up according to the grid's [`Topology`], or `None` if the grid is bounded
+    /// and `pos` is outside of it
+    pub fn resolve(&self, pos: Vec2D) -> Option<Vec2D> {
+        self.topology.resolve(pos, self.num_cols(), self.num_rows())
+    }
+
+    pub fn step(&self, pos: Vec2D, delta: impl Into<Vec2D>) -> Option<Vec2D> {
+        self.resolve(pos + delta)
+    }
+
+    pub(super) fn storage_pos(&self, pos: Vec2D) -> Option<Vec2D> {
+        self.topology.storage_pos(pos, self.num_cols(), self.num_rows())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn bounded() {
+        let grid: Grid<_> = [[1, 2, 3], [4, 5, 6]].into();
+        assert_eq!(grid.topology(), Topology::Bounded);
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.step(Vec2D::new(2, 1), (1, 0)), None);
+        assert_eq!(grid.step(Vec2D::new(2, 1), (-1, -1)), Some(Vec2D::new(1, 0)));
+        assert_eq!(grid.orthogonal_neighbors(&Vec2D::new(0, 0)).count(), 2);
+    }
+
+    #[test]
+    fn toroidal() {
+        let mut grid = Grid::from([[1, 2, 3], [4, 5, 6]]).with_topology(Topology::Toroidal);
+        assert_eq!(grid.get((3, 0)), Some(&1));
+        assert_eq!(grid.get((-1, -1)), Some(&6));
+        assert_eq!(grid[(4, 3)], 5);
+        assert_eq!(grid.step(Vec2D::new(2, 1), (1, 1)), Some(Vec2D::new(0, 0)));
+        assert_eq!(
+            grid.orthogonal_neighbors(&Vec2D::new(0, 0)).collect::<HashSet<_>>(),
+            HashSet::from([Vec2D::new(1, 0), Vec2D::new(2, 0), Vec2D::new(0, 1)])
+        );
+
+        grid[(-1, 0)] = 7;
+        assert_eq!(grid[(2, 0)], 7);
+    }
+
+    #[test]
+    fn tiled() {
+        let grid = Grid::from([[1, 2, 3], [4, 5, 6]]).with_topology(Topology::Tiled);
+        assert_eq!(grid.get((3, 0)), Some(&1));
+        assert_eq!(grid.get((-4, -3)), Some(&6));
+        assert_eq!(grid.step(Vec2D::new(2, 1), (1, 1)), Some(Vec2D::new(3, 2)));
+        assert_eq!(
+            grid.orthogonal_neighbors(&Vec2D::new(0, 0)).collect::<HashSet<_>>(),
+            HashSet::from([
+                Vec2D::new(1, 0),
+                Vec2D::new(-1, 0),
+                Vec2D::new(0, 1),
+                Vec2D::new(0, -1)
+            ])
+        );
+        assert!(!grid.contains(&Vec2D::new(3, 0)));
+    }
+
+    #[test]
+    fn equality_ignores_topology() {
+        let bounded = Grid::from([[1, 2], [3, 4]]);
+        let toroidal = bounded.clone().with_topology(Topology::Toroidal);
+        assert_eq!(bounded, toroidal);
+        assert_eq!(HashSet::from([bounded, toroidal]).len(), 1);
+    }
+}
@@ -0,0 +1,258 @@
+use std::ops::Index;
+
+use crate::math::Vec2D;
+
+use super::Grid;
+
+/// The 8 symmetries of a rectangle, i.e. all combinations of rotations and reflections
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Symmetry {
+    Identity,
+    RotateCw,
+    Rotate180,
+    RotateCcw,
+    FlipHorizontal,
+    FlipVertical,
+    Transpose,
+    AntiTranspose,
+}
+
+impl Symmetry {
+    pub const ALL: [Symmetry; 8] = [
+        Symmetry::Identity,
+        Symmetry::RotateCw,
+        Symmetry::Rotate180,
+        Symmetry::RotateCcw,
+        Symmetry::FlipHorizontal,
+        Symmetry::FlipVertical,
+        Symmetry::Transpose,
+        Symmetry::AntiTranspose,
+    ];
+
+    /// Whether rows and columns are swapped, i.e. whether the transformed grid has
+    /// `num_rows` and `num_cols` of the original grid swapped
+    pub fn swaps_axes(&self) -> bool {
+        matches!(
+            self,
+            Symmetry::RotateCw
+                | Symmetry::RotateCcw
+                | Symmetry::Transpose
+                | Symmetry::AntiTranspose
+        )
+    }
+
+    /// Maps a position in the transformed grid back to the position in a source grid with the
+    /// given dimensions
+    pub fn source_pos(&self, pos: Vec2D, source_cols: usize, source_rows: usize) -> Vec2D {
+        let Vec2D { x: w, y: h } = Vec2D::<i64>::from((source_cols, source_rows));
+        let Vec2D { x, y } = pos;
+        match self {
+            Symmetry::Identity => Vec2D::new(x, y),
+            Symmetry::RotateCw => Vec2D::new(y, h - 1 - x),
+            Symmetry::Rotate180 => Vec2D::new(w - 1 - x, h - 1 - y),
+            Symmetry::RotateCcw => Vec2D::new(w - 1 - y, x),
+            Symmetry::FlipHorizontal => Vec2D::new(w - 1 - x, y),
+            Symmetry::FlipVertical => Vec2D::new(x, h - 1 - y),
+            Symmetry::Transpose => Vec2D::new(y, x),
+            Symmetry::AntiTranspose => Vec2D::new(w - 1 - y, h - 1 - x),
+        }
+    }
+}
+
+/// Zero-copy view of a [`Grid`] under one of its [`Symmetry`]s. Coordinates are remapped on
+/// access instead of moving any data.
+#[derive(Debug)]
+pub struct TransformedGrid<'a, T> {
+    grid: &'a Grid<T>,
+    symmetry: Symmetry,
+}
+
+// Deriving these would require T: Clone/Copy
+impl<T> Clone for TransformedGrid<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for TransformedGrid<'_, T> {}
+
+impl<'a, T> TransformedGrid<'a, T> {
+    pub fn symmetry(&self) -> Symmetry {
+        self.symmetry
+    }
+
+    pub fn num_rows(&self) -> usize {
+        if self.symmetry.swaps_axes() {
+            self.grid.num_cols()
+        } else {
+            self.grid.num_rows()
+        }
+    }
+
+    pub fn num_cols(&self) -> usize {
+        if self.symmetry.swaps_axes() {
+            self.grid.num_rows()
+        } else {
+            self.grid.num_cols()
+        }
+    }
+
+    pub fn contains(&self, pos: &Vec2D) -> bool {
+        pos.inside_size((self.num_cols(), self.num_rows()))
+    }
+
+    pub fn get(&self, pos: impl Into<Vec2D>) -> Option<&'a T> {
+        let pos = pos.into();
+        self.contains(&pos).then(|| {
+            let source = self.symmetry.source_pos(pos, self.grid.num_cols(), self.grid.num_rows());
+            &self.grid[source]
+        })
+    }
+
+    pub fn coordinates_row_major(&self) -> impl DoubleEndedIterator<Item = Vec2D> + 'a {
+        let (num_rows, num_cols) = (self.num_rows(), self.num_cols());
+        (0..num_rows).flat_map(move |y| (0..num_cols).map(move |x| Vec2D::from((x, y))))
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (Vec2D, &'a T)> + 'a {
+        let view = *self;
+        self.coordinates_row_major().map(move |pos| (pos, view.get(pos).unwrap()))
+    }
+}
+
+impl<T: Clone> TransformedGrid<'_, T> {
+    /// Keeps the source grid's [`super::Topology`]
+    pub fn to_owned(&self) -> Grid<T> {
+        Grid::from_flat(self.iter().map(|(_, item)| item.clone()).collect(), self.num_cols())
+            .with_topology(self.grid.topology())
+    }
+}
+
+impl<T, Pos> Index<Pos> for TransformedGrid<'_, T>
+where
+    Pos: Into<Vec2D>,
+{
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.get(pos).unwrap()
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn symmetry_view(&self, symmetry: Symmetry) -> TransformedGrid<'_, T> {
+        TransformedGrid { grid: self, symmetry }
+    }
+
+    /// Views of all 8 symmetries, starting with the identity
+    pub fn symmetry_views(&self) -> impl Iterator<Item = TransformedGrid<'_, T>> + '_ {
+        Symmetry::ALL.into_iter().map(|symmetry| self.symmetry_view(symmetry))
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn transform(&self, symmetry: Symmetry) -> Self {
+        self.symmetry_view(symmetry).to_owned()
+    }
+
+    pub fn transpose(&self) -> Self {
+        self.transform(Symmetry::Transpose)
+    }
+
+    pub fn rotate_cw(&self) -> Self {
+        self.transform(Symmetry::RotateCw)
+    }
+
+    pub fn rotate_ccw(&self) -> Self {
+        self.transform(Symmetry::RotateCcw)
+    }
+
+    /// Mirrors along the vertical axis, i.e. reverses each row
+    pub fn flip_horizontal(&self) -> Self {
+        self.transform(Symmetry::FlipHorizontal)
+    }
+
+    /// Mirrors along the horizontal axis, i.e. reverses each column
+    pub fn flip_vertical(&self) -> Self {
+        self.transform(Symmetry::FlipVertical)
+    }
+
+    /// All 8 rotations and reflections, starting with the identity. Might contain duplicates if
+    /// the grid itself is symmetric.
+    pub fn symmetries(&self) -> impl Iterator<Item = Self> + '_ {
+        Symmetry::ALL.into_iter().map(|symmetry| self.transform(symmetry))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use itertools::Itertools;
+
+    use crate::grid::Topology;
+    use crate::math::Box2D;
+
+    use super::*;
+
+    #[test]
+    fn transforms() {
+        let grid: Grid<_> = [[1, 2, 3], [4, 5, 6]].into();
+
+        assert_eq!(grid.transpose(), Grid::from([[1, 4], [2, 5], [3, 6]]));
+        assert_eq!(grid.rotate_cw(), Grid::from([[4, 1], [5, 2], [6, 3]]));
+        assert_eq!(grid.rotate_ccw(), Grid::from([[3, 6], [2, 5], [1, 4]]));
+        assert_eq!(grid.transform(Symmetry::Rotate180), Grid::from([[6, 5, 4], [3, 2, 1]]));
+        assert_eq!(grid.flip_horizontal(), Grid::from([[3, 2, 1], [6, 5, 4]]));
+        assert_eq!(grid.flip_vertical(), Grid::from([[4, 5, 6], [1, 2, 3]]));
+        assert_eq!(grid.transform(Symmetry::AntiTranspose), Grid::from([[6, 3], [5, 2], [4, 1]]));
+
+        assert_eq!(grid.rotate_cw().rotate_cw(), grid.transform(Symmetry::Rotate180));
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+        assert_eq!(grid.transpose().transpose(), grid);
+    }
+
+    #[test]
+    fn symmetries() {
+        let grid: Grid<_> = [[1, 2, 3], [4, 5, 6]].into();
+        assert_eq!(grid.symmetries().collect::<HashSet<_>>().len(), 8);
+        assert_eq!(grid.symmetries().next(), Some(grid.clone()));
+
+        let symmetric: Grid<_> = [[1, 1], [1, 1]].into();
+        assert_eq!(symmetric.symmetries().unique().count(), 1);
+    }
+
+    #[test]
+    fn views() {
+        let grid: Grid<_> = [[1, 2, 3], [4, 5, 6]].into();
+
+        let view = grid.symmetry_view(Symmetry::RotateCw);
+        assert_eq!((view.num_rows(), view.num_cols()), (3, 2));
+        assert_eq!(view[(0, 0)], 4);
+        assert_eq!(view.get((1, 2)), Some(&3));
+        assert_eq!(view.get((2, 0)), None);
+        assert_eq!(view.iter().map(|(_, &v)| v).collect_vec(), [4, 1, 5, 2, 6, 3]);
+
+        for view in grid.symmetry_views() {
+            assert_eq!(view.to_owned(), grid.transform(view.symmetry()));
+        }
+
+        let toroidal = grid.with_topology(Topology::Toroidal);
+        assert_eq!(
+            toroidal.symmetry_view(Symmetry::FlipHorizontal).to_owned().topology(),
+            Topology::Toroidal
+        );
+    }
+
+    #[test]
+    fn owned_copies_keep_topology() {
+        let grid = Grid::from([[1, 2, 3], [4, 5, 6]]).with_topology(Topology::Toroidal);
+        assert_eq!(grid.rotate_cw().topology(), Topology::Toroidal);
+        assert_eq!(grid.as_view().to_owned().topology(), Topology::Toroidal);
+        assert_eq!(
+            grid.sub_grid(Box2D::new((1, 0), (2, 1))).to_owned().topology(),
+            Topology::Toroidal
+        );
+        assert_eq!(grid.pad_edges(0).topology(), Topology::Toroidal);
+    }
+}
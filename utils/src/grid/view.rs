@@ -0,0 +1,321 @@
+use std::ops::Index;
+
+use itertools::Itertools;
+
+use crate::math::{Box2D, Vec2D};
+
+use super::{Grid, UnwrapIntoUsize};
+
+/// Borrowed rectangular region of a [`Grid`]. Positions are relative to the upper left corner of
+/// the region.
+#[derive(Debug)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    offset: Vec2D,
+    num_cols: usize,
+    num_rows: usize,
+}
+
+impl<T> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GridView<'_, T> {}
+
+impl<T> Grid<T> {
+    /// Panics if the region is empty or not fully inside the grid
+    pub fn sub_grid(&self, region: impl Into<Box2D>) -> GridView<'_, T> {
+        let region = region.into();
+        assert!(!region.is_empty(), "{region:?} is empty");
+        assert!(
+            self.contains(&region.lower) && self.contains(&region.upper),
+            "{region:?} is not inside the grid"
+        );
+        GridView {
+            grid: self,
+            offset: region.lower,
+            num_cols: (region.upper.x - region.lower.x + 1) as usize,
+            num_rows: (region.upper.y - region.lower.y + 1) as usize,
+        }
+    }
+
+    pub fn as_view(&self) -> GridView<'_, T> {
+        GridView {
+            grid: self,
+            offset: Vec2D::zero(),
+            num_cols: self.num_cols(),
+            num_rows: self.num_rows(),
+        }
+    }
+
+    /// All sub-grids of the given size, in row-major order of their upper left corner
+    pub fn windows(
+        &self,
+        num_cols: usize,
+        num_rows: usize,
+    ) -> impl Iterator<Item = GridView<'_, T>> + '_ {
+        self.as_view().windows(num_cols, num_rows)
+    }
+}
+
+impl<'a, T> GridView<'a, T> {
+    /// Upper left corner of the view in coordinates of the underlying grid
+    pub fn offset(&self) -> Vec2D {
+        self.offset
+    }
+
+    /// The region of the underlying grid covered by this view, `None` if the view is empty
+    pub fn region(&self) -> Option<Box2D> {
+        (self.num_cols > 0 && self.num_rows > 0)
+            .then(|| Box2D::new(self.offset, self.offset + (self.num_cols - 1, self.num_rows - 1)))
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    pub fn num_cols(&self) -> usize {
+        self.num_cols
+    }
+
+    pub fn contains(&self, pos: &Vec2D) -> bool {
+        pos.inside_size((self.num_cols, self.num_rows))
+    }
+
+    pub fn get(&self, pos: impl Into<Vec2D>) -> Option<&'a T> {
+        let pos = pos.into();
+        self.contains(&pos).then(|| &self.grid[self.offset + pos])
+    }
+
+    pub fn row(
+        &self,
+        row: impl UnwrapIntoUsize,
+    ) -> impl DoubleEndedIterator<Item = (Vec2D, &'a T)> + 'a {
+        let row = row.unwrap_usize();
+        let start = self.offset.x as usize;
+        self.grid.row_slice(self.offset.y as usize + row)[start..start + self.num_cols]
+            .iter()
+            .enumerate()
+            .map(move |(col, item)| ((col, row).into(), item))
+    }
+
+    pub fn row_values(&self, row: impl UnwrapIntoUsize) -> impl Iterator<Item = &'a T> + 'a {
+        self.row(row).map(|(_, item)| item)
+    }
+
+    pub fn rows(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = impl DoubleEndedIterator<Item = (Vec2D, &'a T)>> + 'a {
+        let view = *self;
+        (0..self.num_rows).map(move |row| view.row(row))
+    }
+
+    pub fn col(
+        &self,
+        col: impl UnwrapIntoUsize,
+    ) -> impl DoubleEndedIterator<Item = (Vec2D, &'a T)> + 'a {
+        let (view, col) = (*self, col.unwrap_usize());
+        (0..self.num_rows).map(move |row| ((col, row).into(), view.get((col, row)).unwrap()))
+    }
+
+    pub fn col_values(&self, col: impl UnwrapIntoUsize) -> impl Iterator<Item = &'a T> + 'a {
+        self.col(col).map(|(_, item)| item)
+    }
+
+    pub fn cols(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = impl DoubleEndedIterator<Item = (Vec2D, &'a T)>> + 'a {
+        let view = *self;
+        (0..self.num_cols).map(move |col| view.col(col))
+    }
+
+    pub fn coordinates_row_major(&self) -> impl DoubleEndedIterator<Item = Vec2D> + 'a {
+        let (num_rows, num_cols) = (self.num_rows, self.num_cols);
+        (0..num_rows).flat_map(move |y| (0..num_cols).map(move |x| Vec2D::from((x, y))))
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (Vec2D, &'a T)> + 'a {
+        self.rows().flatten()
+    }
+
+    pub fn orthogonal_neighbors<'b>(&self, pos: &'b Vec2D) -> impl Iterator<Item = Vec2D> + 'b
+    where
+        'a: 'b,
+    {
+        let view = *self;
+        pos.orthogonal_neighbors().filter(move |neighbor| view.contains(neighbor))
+    }
+
+    pub fn diagonal_neighbors<'b>(&self, pos: &'b Vec2D) -> impl Iterator<Item = Vec2D> + 'b
+    where
+        'a: 'b,
+    {
+        let view = *self;
+        pos.diagonal_neighbors().filter(move |neighbor| view.contains(neighbor))
+    }
+
+    pub fn all_neighbors<'b>(&self, pos: &'b Vec2D) -> impl Iterator<Item = Vec2D> + 'b
+    where
+        'a: 'b,
+    {
+        let view = *self;
+        pos.all_neighbors().filter(move |neighbor| view.contains(neighbor))
+    }
+
+    /// Views can be narrowed down further, the region is relative to this view
+    pub fn sub_grid(&self, region: impl Into<Box2D>) -> GridView<'a, T> {
+        let region = region.into();
+        assert!(!region.is_empty(), "{region:?} is empty");
+        assert!(
+            self.contains(&region.lower) && self.contains(&region.upper),
+            "{region:?} is not inside the view"
+        );
+        GridView {
+            grid: self.grid,
+            offset: self.offset + region.lower,
+            num_cols: (region.upper.x - region.lower.x + 1) as usize,
+            num_rows: (region.upper.y - region.lower.y + 1) as usize,
+        }
+    }
+
+    /// All sub-grids of the given size, in row-major order of their upper left corner
+    pub fn windows(
+        &self,
+        num_cols: usize,
+        num_rows: usize,
+    ) -> impl Iterator<Item = GridView<'a, T>> + 'a {
+        let view = *self;
+        let num_windows = |len: usize, size: usize| {
+            if size == 0 {
+                0
+            } else {
+                (len + 1).saturating_sub(size)
+            }
+        };
+        let (max_x, max_y) =
+            (num_windows(self.num_cols, num_cols), num_windows(self.num_rows, num_rows));
+        (0..max_y).cartesian_product(0..max_x).map(move |(y, x)| GridView {
+            grid: view.grid,
+            offset: view.offset + (x, y),
+            num_cols,
+            num_rows,
+        })
+    }
+}
+
+impl<T: Clone> GridView<'_, T> {
+    /// Keeps the source grid's [`super::Topology`]
+    pub fn to_owned(&self) -> Grid<T> {
+        let grid: Grid<T> = self.rows().map(|row| row.map(|(_, item)| item.clone())).collect();
+        grid.with_topology(self.grid.topology())
+    }
+}
+
+impl<T: PartialEq> GridView<'_, T> {
+    pub fn find_position(&self, val: &T) -> Option<Vec2D> {
+        self.iter().find_map(|(pos, v)| (v == val).then_some(pos))
+    }
+}
+
+impl<T, Pos> Index<Pos> for GridView<'_, T>
+where
+    Pos: Into<Vec2D>,
+{
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.get(pos).unwrap()
+    }
+}
+
+impl<T> std::fmt::Display for GridView<'_, T>
+where
+    T: std::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for (_, x) in row {
+                write!(f, "{x}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<i32> {
+        [[1, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12]].into()
+    }
+
+    #[test]
+    fn sub_grid() {
+        let grid = grid();
+        let view = grid.sub_grid(Box2D::new((1, 1), (3, 2)));
+
+        assert_eq!((view.num_rows(), view.num_cols()), (2, 3));
+        assert_eq!(view.offset(), Vec2D::new(1, 1));
+        assert_eq!(view.region(), Some(Box2D::new((1, 1), (3, 2))));
+        assert_eq!(view[(0, 0)], 6);
+        assert_eq!(view.get((2, 1)), Some(&12));
+        assert_eq!(view.get((3, 0)), None);
+        assert_eq!(view.get((-1, 0)), None);
+
+        assert_eq!(view.row_values(1).copied().collect_vec(), [10, 11, 12]);
+        assert_eq!(view.col(2).collect_vec(), [(Vec2D::new(2, 0), &8), (Vec2D::new(2, 1), &12)]);
+        assert_eq!(view.cols().count(), 3);
+        assert_eq!(view.iter().map(|(_, &v)| v).collect_vec(), [6, 7, 8, 10, 11, 12]);
+        assert_eq!(view.find_position(&11), Some(Vec2D::new(1, 1)));
+
+        assert_eq!(view.orthogonal_neighbors(&Vec2D::new(0, 0)).count(), 2);
+        assert_eq!(view.all_neighbors(&Vec2D::new(1, 0)).count(), 5);
+
+        assert_eq!(view.to_owned(), Grid::from([[6, 7, 8], [10, 11, 12]]));
+        assert_eq!(view.sub_grid(Box2D::new((1, 1), (2, 1))).to_owned(), Grid::from([[11, 12]]));
+        assert_eq!(view.to_string(), "678\n101112\n");
+
+        assert_eq!(grid.as_view().to_owned(), grid);
+        assert_eq!(Grid::from(Vec::<Vec<i32>>::new()).as_view().region(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn sub_grid_out_of_bounds() {
+        grid().sub_grid(Box2D::new((1, 1), (4, 2)));
+    }
+
+    #[test]
+    #[should_panic(expected = "is empty")]
+    fn sub_grid_inverted() {
+        grid().sub_grid(Box2D::new((2, 2), (0, 0)));
+    }
+
+    #[test]
+    fn windows() {
+        let grid = grid();
+        assert_eq!(
+            grid.windows(3, 2).map(|window| window.to_owned()).collect_vec(),
+            [
+                Grid::from([[1, 2, 3], [5, 6, 7]]),
+                Grid::from([[2, 3, 4], [6, 7, 8]]),
+                Grid::from([[5, 6, 7], [9, 10, 11]]),
+                Grid::from([[6, 7, 8], [10, 11, 12]]),
+            ]
+        );
+        assert_eq!(grid.windows(1, 1).count(), 12);
+        assert_eq!(grid.windows(4, 3).count(), 1);
+        assert_eq!(grid.windows(5, 1).count(), 0);
+        assert_eq!(grid.windows(0, 1).count(), 0);
+
+        let view = grid.sub_grid(Box2D::new((1, 0), (3, 2)));
+        assert_eq!(
+            view.windows(2, 2).map(|w| w.offset()).collect_vec(),
+            [(1, 0), (2, 0), (1, 1), (2, 1)]
+        );
+    }
+}
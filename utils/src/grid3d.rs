@@ -0,0 +1,160 @@
+use std::ops::{Index, IndexMut};
+
+use crate::math::{Box3D, Vec3D};
+
+/// Dense 3D grid from `(0, 0, 0)` (inclusive) to [`Grid3D::size`] (exclusive), stored as a single
+/// `Vec` in x-major order, i.e. x changes fastest
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Grid3D<T> {
+    data: Vec<T>,
+    size: Vec3D,
+}
+
+impl<T: Clone> Grid3D<T> {
+    pub fn with_value(val: T, size: impl Into<Vec3D>) -> Self {
+        let size = size.into();
+        assert!(size.x >= 0 && size.y >= 0 && size.z >= 0, "Invalid size {size}");
+        Self { data: vec![val; (size.x * size.y * size.z) as usize], size }
+    }
+
+    /// Grid just large enough to contain all `points`, which are set to `set`. All other cells are
+    /// `unset`. Panics on negative coordinates.
+    pub fn from_points(points: impl IntoIterator<Item = Vec3D>, unset: T, set: T) -> Self {
+        let points: Vec<_> = points.into_iter().collect();
+        let size = points.iter().fold(Vec3D::zero(), |size, p| {
+            Vec3D::new(size.x.max(p.x + 1), size.y.max(p.y + 1), size.z.max(p.z + 1))
+        });
+        let mut grid = Self::with_value(unset, size);
+        for point in points {
+            grid[point] = set.clone();
+        }
+        grid
+    }
+}
+
+impl<T> Grid3D<T> {
+    pub fn size(&self) -> Vec3D {
+        self.size
+    }
+
+    /// Box from `(0, 0, 0)` to `size - (1, 1, 1)`
+    pub fn bounds(&self) -> Box3D {
+        Box3D::new(Vec3D::zero(), self.size - (1, 1, 1))
+    }
+
+    pub fn contains(&self, pos: &Vec3D) -> bool {
+        pos.inside_box(Vec3D::zero(), self.size - (1, 1, 1))
+    }
+
+    fn flat_index(&self, pos: &Vec3D) -> usize {
+        ((pos.z * self.size.y + pos.y) * self.size.x + pos.x) as usize
+    }
+
+    fn pos_from_flat_index(&self, index: usize) -> Vec3D {
+        let (index, Vec3D { x: num_x, y: num_y, .. }) = (index as i64, self.size);
+        Vec3D::new(index % num_x, index / num_x % num_y, index / (num_x * num_y))
+    }
+
+    pub fn get(&self, pos: impl Into<Vec3D>) -> Option<&T> {
+        let pos = pos.into();
+        self.contains(&pos).then(|| &self.data[self.flat_index(&pos)])
+    }
+
+    pub fn get_mut(&mut self, pos: impl Into<Vec3D>) -> Option<&mut T> {
+        let pos = pos.into();
+        if !self.contains(&pos) {
+            return None;
+        }
+        let index = self.flat_index(&pos);
+        Some(&mut self.data[index])
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Vec3D, &T)> + '_ {
+        self.data.iter().enumerate().map(|(i, item)| (self.pos_from_flat_index(i), item))
+    }
+
+    /// Positions of the up to 6 cells sharing a face with `pos`
+    pub fn orthogonal_neighbors(&self, pos: &Vec3D) -> impl Iterator<Item = Vec3D> + '_ {
+        pos.orthogonal_neighbors().filter(|n| self.contains(n))
+    }
+
+    /// Positions of the up to 26 cells surrounding `pos`
+    pub fn all_neighbors(&self, pos: &Vec3D) -> impl Iterator<Item = Vec3D> + '_ {
+        pos.all_neighbors().filter(|n| self.contains(n))
+    }
+}
+
+impl<T: PartialEq> Grid3D<T> {
+    pub fn find_position(&self, val: &T) -> Option<Vec3D> {
+        self.iter().find_map(|(pos, v)| (v == val).then_some(pos))
+    }
+}
+
+impl<T, Pos> Index<Pos> for Grid3D<T>
+where
+    Pos: Into<Vec3D>,
+{
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        let pos = pos.into();
+        self.get(pos).unwrap_or_else(|| panic!("{pos} is outside of the grid"))
+    }
+}
+
+impl<T, Pos> IndexMut<Pos> for Grid3D<T>
+where
+    Pos: Into<Vec3D>,
+{
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        let pos = pos.into();
+        self.get_mut(pos).unwrap_or_else(|| panic!("{pos} is outside of the grid"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    #[test]
+    fn index() {
+        let mut grid = Grid3D::with_value(0, (2, 3, 4));
+        assert_eq!(grid.as_slice().len(), 24);
+        assert_eq!(grid.bounds(), Box3D::new((0, 0, 0), (1, 2, 3)));
+
+        grid[(1, 2, 3)] = 5;
+        assert_eq!(grid.get((1, 2, 3)), Some(&5));
+        assert_eq!(grid.get((2, 2, 3)), None);
+        assert_eq!(grid.get((0, -1, 0)), None);
+        assert_eq!(grid.find_position(&5), Some(Vec3D::new(1, 2, 3)));
+        assert_eq!(grid.as_slice().last(), Some(&5));
+
+        assert_eq!(
+            grid.iter().map(|(pos, _)| pos).collect_vec(),
+            grid.bounds().points_inside().sorted_by_key(|p| (p.z, p.y, p.x)).collect_vec()
+        );
+    }
+
+    #[test]
+    fn from_points() {
+        // Surface area of two adjacent cubes
+        let cubes = ["1,1,1", "2,1,1"].iter().map(|s| s.parse::<Vec3D>().unwrap()).collect_vec();
+        let grid = Grid3D::from_points(cubes.iter().copied(), false, true);
+        assert_eq!(grid.size(), (3, 2, 2));
+        let exposed = cubes
+            .iter()
+            .flat_map(|cube| cube.orthogonal_neighbors())
+            .filter(|n| !grid.get(n).is_some_and(|&lava| lava))
+            .count();
+        assert_eq!(exposed, 10);
+
+        assert_eq!(grid.orthogonal_neighbors(&Vec3D::zero()).count(), 3);
+        assert_eq!(grid.all_neighbors(&Vec3D::zero()).count(), 7);
+    }
+}
@@ -0,0 +1,139 @@
+//! Export of grids to binary PPM (color) and PGM (grayscale) images, which most image viewers can
+//! open directly.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::grid::Grid;
+use crate::math::{Box2D, Vec2D};
+use crate::sparse_grid::SparseGrid;
+
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+
+fn encode(magic: &str, width: usize, height: usize, pixels: impl Iterator<Item = u8>) -> Vec<u8> {
+    let mut image = format!("{magic}\n{width} {height}\n255\n").into_bytes();
+    image.extend(pixels);
+    image
+}
+
+fn encode_ppm(width: usize, height: usize, pixels: impl Iterator<Item = Rgb>) -> Vec<u8> {
+    encode("P6", width, height, pixels.flatten())
+}
+
+fn encode_pgm(width: usize, height: usize, pixels: impl Iterator<Item = u8>) -> Vec<u8> {
+    encode("P5", width, height, pixels)
+}
+
+/// Writes an image returned by one of the `to_ppm`/`to_pgm` functions, panics on IO errors
+pub fn save(path: impl AsRef<Path>, image: &[u8]) {
+    let path = path.as_ref();
+    std::fs::write(path, image).unwrap_or_else(|e| panic!("Failed to write {path:?}: {e}"));
+}
+
+impl<T> Grid<T> {
+    pub fn to_ppm(&self, color: impl Fn(&T) -> Rgb) -> Vec<u8> {
+        encode_ppm(self.num_cols(), self.num_rows(), self.as_slice().iter().map(color))
+    }
+
+    pub fn to_pgm(&self, gray: impl Fn(&T) -> u8) -> Vec<u8> {
+        encode_pgm(self.num_cols(), self.num_rows(), self.as_slice().iter().map(gray))
+    }
+}
+
+/// Positions of the bounding box in row-major order, with the box's dimensions
+fn row_major(bounds: Option<Box2D>) -> (usize, usize, impl Iterator<Item = Vec2D>) {
+    let Some(Box2D { lower, upper }) = bounds else {
+        return (0, 0, None.into_iter().flatten());
+    };
+    let (width, height) = ((upper.x - lower.x + 1) as usize, (upper.y - lower.y + 1) as usize);
+    let positions =
+        (lower.y..=upper.y).flat_map(move |y| (lower.x..=upper.x).map(move |x| Vec2D::new(x, y)));
+    (width, height, Some(positions).into_iter().flatten())
+}
+
+impl<T> SparseGrid<T> {
+    /// The image covers the bounding box of all set cells, empty cells get the `background` color
+    pub fn to_ppm(&self, background: Rgb, color: impl Fn(&T) -> Rgb) -> Vec<u8> {
+        let (width, height, positions) = row_major(self.bounds());
+        encode_ppm(
+            width,
+            height,
+            positions.map(|pos| self.data_at(&pos).map_or(background, &color)),
+        )
+    }
+
+    /// Like [`SparseGrid::to_ppm`], but grayscale
+    pub fn to_pgm(&self, background: u8, gray: impl Fn(&T) -> u8) -> Vec<u8> {
+        let (width, height, positions) = row_major(self.bounds());
+        encode_pgm(width, height, positions.map(|pos| self.data_at(&pos).map_or(background, &gray)))
+    }
+
+    fn data_at(&self, pos: &Vec2D) -> Option<&T> {
+        std::ops::Deref::deref(self).get(pos)
+    }
+}
+
+/// Maps `value` from `0..=max` onto a blue -> cyan -> green -> yellow -> red gradient
+pub fn heat_color(value: usize, max: usize) -> Rgb {
+    const GRADIENT: [Rgb; 5] =
+        [[0, 0, 255], [0, 255, 255], [0, 255, 0], [255, 255, 0], [255, 0, 0]];
+    if max == 0 {
+        return GRADIENT[0];
+    }
+
+    let t = value.min(max) as f64 / max as f64 * (GRADIENT.len() - 1) as f64;
+    let segment = (t as usize).min(GRADIENT.len() - 2);
+    let (from, to, t) = (GRADIENT[segment], GRADIENT[segment + 1], t - segment as f64);
+    std::array::from_fn(|i| (from[i] as f64 + (to[i] as f64 - from[i] as f64) * t).round() as u8)
+}
+
+/// Colors a distance field, e.g. from [`crate::graphs::floodfill`], from blue (closest) to red
+/// (farthest). Save it with `heatmap(..).to_ppm(BLACK, |&c| c)`.
+pub fn heatmap(values: &HashMap<Vec2D, usize>) -> SparseGrid<Rgb> {
+    let max = values.values().copied().max().unwrap_or(0);
+    values.iter().map(|(&pos, &value)| (pos, heat_color(value, max))).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid() {
+        let grid: Grid<_> = [['#', '.'], ['.', '#']].into();
+        assert_eq!(
+            grid.to_pgm(|&c| if c == '#' { 255 } else { 0 }),
+            b"P5\n2 2\n255\n\xff\x00\x00\xff"
+        );
+        assert_eq!(
+            grid.to_ppm(|&c| if c == '#' { [1, 2, 3] } else { BLACK }),
+            b"P6\n2 2\n255\n\x01\x02\x03\x00\x00\x00\x00\x00\x00\x01\x02\x03"
+        );
+    }
+
+    #[test]
+    fn sparse_grid() {
+        let grid: SparseGrid<_> = [((-1, 5), 10), ((1, 6), 20)].into_iter().collect();
+        assert_eq!(grid.to_pgm(0, |&v| v), b"P5\n3 2\n255\n\x0a\x00\x00\x00\x00\x14");
+        assert_eq!(grid.to_ppm(WHITE, |_| BLACK).len(), "P6\n3 2\n255\n".len() + 18);
+        assert_eq!(SparseGrid::<u8>::new().to_pgm(0, |&v| v), b"P5\n0 0\n255\n");
+    }
+
+    #[test]
+    fn heatmap() {
+        assert_eq!(heat_color(0, 8), [0, 0, 255]);
+        assert_eq!(heat_color(2, 8), [0, 255, 255]);
+        assert_eq!(heat_color(3, 8), [0, 255, 128]);
+        assert_eq!(heat_color(8, 8), [255, 0, 0]);
+        assert_eq!(heat_color(9, 8), [255, 0, 0]);
+        assert_eq!(heat_color(0, 0), [0, 0, 255]);
+
+        let distances = HashMap::from([(Vec2D::new(0, 0), 0), (Vec2D::new(1, 0), 4)]);
+        let map = super::heatmap(&distances);
+        assert_eq!(map.get((0, 0)), Some(&[0, 0, 255]));
+        assert_eq!(map.get((1, 0)), Some(&[255, 0, 0]));
+    }
+}
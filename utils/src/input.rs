@@ -1,6 +1,7 @@
 use std::str::{Chars, FromStr};
 
 use itertools::Itertools;
+use lazy_regex::regex;
 
 use crate::{grid::Grid, math::Vec2D, sparse_grid::SparseGrid};
 
@@ -13,14 +14,82 @@ impl<T> From<T> for Input
 where
     T: ToString,
 {
+    /// Unlike [`Input::new`], this also removes the common indentation of all lines (see
+    /// [`dedent`]), so that examples can be written as indented raw strings in tests.
     fn from(value: T) -> Self {
-        Self { raw: value.to_string().trim().to_string() }
+        Self { raw: normalize(&dedent(&normalize_line_endings(&value.to_string()))) }
     }
 }
 
+/// Removes the leading whitespace that all non-blank lines have in common, like the `indoc` crate
+/// does. Lines that are indented further than that keep their additional leading whitespace. If
+/// the first line starts directly after the opening quote (i.e. without any leading whitespace),
+/// it is left as it is and doesn't count towards the common indentation.
+pub fn dedent(s: &str) -> String {
+    let skip_first = !s.starts_with(char::is_whitespace);
+    let indented = |(i, line): &(usize, &str)| !(skip_first && *i == 0) && !is_blank(line);
+    let indent = s
+        .lines()
+        .enumerate()
+        .filter(indented)
+        .map(|(_, line)| &line[..line.len() - line.trim_start().len()])
+        .reduce(common_prefix)
+        .unwrap_or("");
+
+    s.lines()
+        .enumerate()
+        .map(|(i, line)| match (i, line) {
+            (0, line) if skip_first => line,
+            (_, line) if is_blank(line) => "",
+            (_, line) => line.strip_prefix(indent).unwrap_or(line),
+        })
+        .join("\n")
+}
+
+/// Compared by characters, so that e.g. a tab and a space are never treated as the same indent
+fn common_prefix<'a>(a: &'a str, b: &str) -> &'a str {
+    let len = a
+        .char_indices()
+        .zip(b.chars())
+        .find(|((_, x), y)| x != y)
+        .map_or(a.len().min(b.len()), |((i, _), _)| i);
+    &a[..len]
+}
+
+/// Every constructor goes through this, so that all line and block APIs see the same input:
+/// Line endings are converted to `\n`, and leading and trailing blank lines are removed. Leading
+/// whitespace of the first line and trailing whitespace of the last line are kept, since they
+/// might be significant (e.g. for grids).
+pub(crate) fn normalize(raw: &str) -> String {
+    let raw = normalize_line_endings(raw);
+    if is_blank(&raw) {
+        return String::new();
+    }
+    let first_non_whitespace = raw.len() - raw.trim_start().len();
+    let start = raw[..first_non_whitespace].rfind('\n').map_or(0, |i| i + 1);
+    let last_non_whitespace = raw.trim_end().len();
+    let end = raw[last_non_whitespace..].find('\n').map_or(raw.len(), |i| last_non_whitespace + i);
+    raw[start..end].to_string()
+}
+
+/// Converts `\r\n` and lone `\r` to `\n`
+pub(crate) fn normalize_line_endings(raw: &str) -> String {
+    raw.replace("\r\n", "\n").replace('\r', "\n")
+}
+
+pub(crate) fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
 impl Input {
     pub fn new(input_file: &str) -> Input {
-        Input { raw: std::fs::read_to_string(input_file).unwrap() }
+        Input { raw: normalize(&std::fs::read_to_string(input_file).unwrap()) }
+    }
+
+    /// Strips trailing whitespace from every line. Not done by default since trailing whitespace
+    /// might be significant for some inputs.
+    pub fn strip_trailing_whitespace(self) -> Self {
+        Self { raw: self.lines().map(str::trim_end).join("\n") }
     }
 
     pub fn as_str(&self) -> &str {
@@ -37,15 +106,14 @@ impl Input {
     }
 
     pub fn parse_blocks<T: FromStr>(&self) -> Vec<Vec<T>> {
-        self.lines()
-            .collect_vec()
-            .split(|line| line.is_empty())
-            .map(|lines| lines.iter().map(|line| line.parse().ok().unwrap()).collect())
+        self.blocks()
+            .map(|block| block.lines().map(|line| line.parse().ok().unwrap()).collect())
             .collect()
     }
 
+    /// Blocks are separated by one or more blank lines, which may contain whitespace
     pub fn blocks(&self) -> impl Iterator<Item = &str> {
-        self.raw.split("\n\n")
+        regex!(r"\n\s*\n").split(&self.raw).filter(|block| !is_blank(block))
     }
 
     pub fn split_and_parse<T: FromStr>(&self, sep: &'static str) -> impl Iterator<Item = T> + '_
@@ -103,6 +171,23 @@ impl Input {
         self.lines().map(str::chars).collect()
     }
 
+    /// One byte per cell without any UTF-8 decoding, so only use this for ASCII inputs
+    pub fn byte_grid(&self) -> Grid<u8> {
+        self.lines_bytes().map(|line| line.iter().copied()).collect()
+    }
+
+    /// Like [`Input::number_grid`], but without going through [`FromStr`] for every cell
+    pub fn digit_grid(&self) -> Grid<u8> {
+        self.lines_bytes()
+            .map(|line| {
+                line.iter().map(|&b| {
+                    assert!(b.is_ascii_digit(), "Expected a digit, got {:?}", b as char);
+                    b - b'0'
+                })
+            })
+            .collect()
+    }
+
     pub fn char_sparse_grid(&self) -> SparseGrid<char> {
         self.char_grid().iter().map(|(pos, c)| (pos, *c)).collect()
     }
@@ -111,7 +196,13 @@ impl Input {
     where
         <T as std::str::FromStr>::Err: std::fmt::Debug,
     {
-        self.lines().map(|line| line.chars().map(|c| c.to_string().parse().unwrap())).collect()
+        self.lines()
+            .map(|line| line.chars().map(|c| c.encode_utf8(&mut [0; 4]).parse().unwrap()))
+            .collect()
+    }
+
+    pub fn lines_bytes(&self) -> impl Iterator<Item = &[u8]> + Clone + '_ {
+        self.lines().map(str::as_bytes)
     }
 
     pub fn chars(&self) -> impl Iterator<Item = Chars<'_>> + '_ {
@@ -163,6 +254,63 @@ mod tests {
         assert_eq!(vec![vec![1, 2], vec![3]], input.parse_blocks());
     }
 
+    #[test]
+    fn blocks() {
+        let input = Input::from("a\nb\n  \n\nc\r\n\t\r\nd\n");
+        assert_eq!(vec!["a\nb", "c", "d"], input.blocks().collect_vec());
+
+        let input = Input::from("1\r\n2\r\n \r\n3\r\n");
+        assert_eq!(vec![vec![1, 2], vec![3]], input.parse_blocks());
+    }
+
+    #[test]
+    fn normalize() {
+        assert_eq!(Input::from("\n  \n  a\r\nb  \n\n").raw, "  a\nb  ");
+        assert_eq!(super::normalize("a \n b\r\n \n"), "a \n b");
+        assert_eq!(Input::from(" \n\t\n").raw, "");
+        assert_eq!(Input::from("#. \n.# ").char_grid().num_cols(), 3);
+        assert_eq!(Input::from("a  \nb").strip_trailing_whitespace().raw, "a\nb");
+
+        let file = std::env::temp_dir()
+            .join(format!("aoc_utils_input_normalize_{}.in", std::process::id()));
+        std::fs::write(&file, "a\r\n\r\nb\r\n").unwrap();
+        let input = Input::new(file.to_str().unwrap());
+        std::fs::remove_file(&file).unwrap();
+        assert_eq!(input.raw, Input::from("a\n\nb").raw);
+    }
+
+    #[test]
+    fn dedent() {
+        assert_eq!(
+            Input::from(
+                "
+                #.#
+                  ..
+                \t
+                .#.
+                "
+            )
+            .raw,
+            "#.#\n  ..\n\n.#."
+        );
+
+        assert_eq!(super::dedent("\n  a\n    b\n \n  c"), "\na\n  b\n\nc");
+        assert_eq!(super::dedent("a\n    b\n      c"), "a\nb\n  c");
+        assert_eq!(super::dedent("  a\n    b"), "a\n  b");
+        assert_eq!(Input::from("\r  a\r  b").raw, "a\nb");
+        assert_eq!(Input::from("\r\n  a\r\n    b").raw, "a\n  b");
+    }
+
+    #[test]
+    fn dedent_mixed_whitespace() {
+        assert_eq!(super::dedent("\n\t  a\n\t    b\n\t  c"), "\na\n  b\nc");
+        // Only the exact shared prefix is removed, a tab doesn't count as spaces
+        assert_eq!(super::dedent("\n\t\ta\n\t  b"), "\n\ta\n  b");
+        assert_eq!(super::dedent("\n  a\n\tb"), "\n  a\n\tb");
+        // Unicode whitespace must not be split in the middle of a character
+        assert_eq!(super::dedent("\n\u{3000}\u{3000}a\n\u{3000} b"), "\n\u{3000}a\n b");
+    }
+
     #[test]
     fn numbers() {
         let input = Input { raw: "1,2,3".to_string() };
@@ -236,4 +384,20 @@ mod tests {
         let input = Input { raw: "1234\n4567\n".to_string() };
         assert_eq!(Grid::from([[1, 2, 3, 4], [4, 5, 6, 7]]), input.parse_grid_from_characters())
     }
+
+    #[test]
+    fn bytes() {
+        let input = Input { raw: "#.\n.#\n".to_string() };
+        assert_eq!(vec![b"#.", b".#"], input.lines_bytes().collect_vec());
+        assert_eq!(Grid::from([*b"#.", *b".#"]), input.byte_grid());
+
+        let input = Input { raw: "1234\n4567\n".to_string() };
+        assert_eq!(Grid::from([[1, 2, 3, 4], [4, 5, 6, 7]]), input.digit_grid());
+    }
+
+    #[test]
+    #[should_panic]
+    fn digit_grid_panic() {
+        Input { raw: "12a".to_string() }.digit_grid();
+    }
 }
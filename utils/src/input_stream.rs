@@ -0,0 +1,185 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    str::FromStr,
+};
+
+use itertools::Itertools;
+
+use crate::input::is_blank;
+
+/// Reads the input line by line instead of loading it into memory at once like [`crate::Input`]
+/// does, for inputs that are too large for that.
+///
+/// Lines are normalized the same way as for [`crate::Input`]: Line endings are stripped and
+/// leading and trailing blank lines are skipped. The only difference is that blank lines are always yielded as empty strings, since only their
+/// number is kept while looking for the end of the input.
+#[derive(Debug)]
+pub struct InputStream<R> {
+    reader: R,
+}
+
+impl InputStream<BufReader<File>> {
+    pub fn open(input_file: &str) -> Self {
+        Self::new(BufReader::new(File::open(input_file).unwrap()))
+    }
+}
+
+impl<R: BufRead> InputStream<R> {
+    pub fn new(reader: R) -> Self {
+        Self { reader }
+    }
+
+    pub fn lines(self) -> StreamLines<R> {
+        StreamLines {
+            reader: self.reader,
+            after_cr: false,
+            seen_content: false,
+            blank_lines: 0,
+            ready_blank_lines: 0,
+            ready: None,
+        }
+    }
+
+    pub fn parse_lines<T: FromStr>(self) -> impl Iterator<Item = T>
+    where
+        <T as std::str::FromStr>::Err: std::fmt::Debug,
+    {
+        self.lines().map(|line| line.parse::<T>().unwrap())
+    }
+
+    /// Blocks are separated by one or more blank lines, same as for [`crate::Input::blocks`]
+    pub fn blocks(self) -> impl Iterator<Item = String> {
+        let mut lines = self.lines().peekable();
+        std::iter::from_fn(move || {
+            lines.peek()?;
+            let block = lines.by_ref().take_while(|line| !is_blank(line)).join("\n");
+            while lines.next_if(|line| is_blank(line)).is_some() {}
+            Some(block)
+        })
+    }
+}
+
+pub struct StreamLines<R> {
+    reader: R,
+    /// The previous line ended with `\r`, so a `\n` directly after it belongs to the same line
+    /// ending
+    after_cr: bool,
+    /// Blank lines before the first non-blank line are skipped
+    seen_content: bool,
+    /// Number of blank lines held back until we know they're not at the end of the input
+    blank_lines: usize,
+    /// Number of blank lines that are known to be followed by another line
+    ready_blank_lines: usize,
+    /// The non-blank line after the ready blank lines
+    ready: Option<String>,
+}
+
+impl<R: BufRead> StreamLines<R> {
+    /// Like [`BufRead::read_line`], but a lone `\r` also ends a line
+    fn read_line(&mut self) -> Option<String> {
+        let mut line = Vec::new();
+        loop {
+            let buf = self.reader.fill_buf().unwrap();
+            let Some(&first) = buf.first() else {
+                return (!line.is_empty()).then(|| String::from_utf8(line).unwrap());
+            };
+            if std::mem::take(&mut self.after_cr) && first == b'\n' {
+                self.reader.consume(1);
+                continue;
+            }
+
+            let Some(end) = buf.iter().position(|&b| b == b'\n' || b == b'\r') else {
+                line.extend_from_slice(buf);
+                let len = buf.len();
+                self.reader.consume(len);
+                continue;
+            };
+            line.extend_from_slice(&buf[..end]);
+            self.after_cr = buf[end] == b'\r';
+            self.reader.consume(end + 1);
+            return Some(String::from_utf8(line).unwrap());
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for StreamLines<R> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.ready_blank_lines > 0 {
+            self.ready_blank_lines -= 1;
+            return Some(String::new());
+        }
+        if let Some(line) = self.ready.take() {
+            return Some(line);
+        }
+
+        loop {
+            let line = self.read_line()?;
+            if is_blank(&line) {
+                if self.seen_content {
+                    self.blank_lines += 1;
+                }
+                continue;
+            }
+
+            self.seen_content = true;
+            self.ready_blank_lines = std::mem::take(&mut self.blank_lines);
+            self.ready = Some(line);
+            return self.next();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    fn stream(s: &str) -> InputStream<Cursor<&str>> {
+        InputStream::new(Cursor::new(s))
+    }
+
+    #[test]
+    fn lines() {
+        assert_eq!(
+            stream("\n \na\r\nb\n\n  \nc\n\n").lines().collect_vec(),
+            ["a", "b", "", "", "c"]
+        );
+        assert_eq!(stream("").lines().collect_vec(), Vec::<String>::new());
+        assert_eq!(stream("a\rb\r\rc\r\nd\r").lines().collect_vec(), ["a", "b", "", "c", "d"]);
+    }
+
+    #[test]
+    fn line_endings_across_buffer_boundaries() {
+        let raw = "ab\r\ncd\r\refg\n";
+        let lines = InputStream::new(std::io::BufReader::with_capacity(3, Cursor::new(raw)))
+            .lines()
+            .collect_vec();
+        assert_eq!(lines, ["ab", "cd", "", "efg"]);
+    }
+
+    #[test]
+    fn parse_lines() {
+        assert_eq!(stream("1\n2\n123\n").parse_lines::<i32>().collect_vec(), [1, 2, 123]);
+    }
+
+    #[test]
+    fn blocks() {
+        assert_eq!(stream("a\nb\n  \n\nc\r\n\t\r\nd\n").blocks().collect_vec(), ["a\nb", "c", "d"]);
+    }
+
+    #[test]
+    fn same_as_input() {
+        let raw = "\n1\n2  \n\n3\r\n4\r\n5\t\r6  \n\n";
+        let file =
+            std::env::temp_dir().join(format!("aoc_utils_input_stream_{}.in", std::process::id()));
+        std::fs::write(&file, raw).unwrap();
+        let input = crate::Input::new(file.to_str().unwrap());
+        std::fs::remove_file(&file).unwrap();
+        assert_eq!(stream(raw).lines().collect_vec(), input.lines().collect_vec());
+        assert_eq!(stream(raw).blocks().collect_vec(), input.blocks().collect_vec());
+    }
+}
@@ -5,11 +5,22 @@ pub mod math;
 mod input;
 pub use input::*;
 
+mod input_stream;
+pub use input_stream::*;
+
 pub mod graphs;
 
+pub mod chunked_grid;
 pub mod grid;
+pub mod grid3d;
 pub mod sparse_grid;
 
+pub mod image;
+pub mod recorder;
+
+pub mod automaton;
+pub mod cycle;
+
 mod regex_helper;
 pub use regex_helper::*;
 
@@ -156,23 +167,27 @@ impl Display for Solution {
     }
 }
 
+/// The example input is passed through [`Input::from`], so it may be written as an indented raw
+/// string, see [`dedent`].
 #[macro_export]
 macro_rules! assert_example {
     ($input:expr, $part1:expr, $part2:expr) => {
+        let input = Input::from($input);
         pretty_assertions::assert_eq!(
-            solve(Input::from($input.trim())).into(),
+            solve(input.clone()).into(),
             Solution::from(($part1, $part2)),
             "\n\n{}",
-            $input.trim()
+            input.raw
         );
     };
     ($input:expr, $part1:expr) => {
-        let solution = solve(Input::from($input.trim())).into();
+        let input = Input::from($input);
+        let solution = solve(input.clone()).into();
         pretty_assertions::assert_eq!(
             solution.part1,
             Some($part1.to_string()),
             "\n\n{}",
-            $input.trim()
+            input.raw
         );
     };
 }
@@ -180,12 +195,13 @@ macro_rules! assert_example {
 #[macro_export]
 macro_rules! assert_part2 {
     ($input:expr, $part2:expr) => {
-        let solution = solve(Input::from($input.trim())).into();
+        let input = Input::from($input);
+        let solution = solve(input.clone()).into();
         pretty_assertions::assert_eq!(
             solution.part2,
             Some($part2.to_string()),
             "\n\n{}",
-            $input.trim()
+            input.raw
         );
     };
 }
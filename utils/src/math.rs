@@ -2,5 +2,13 @@ mod vec2d;
 pub use vec2d::*;
 mod box2d;
 pub use box2d::*;
+mod box_set;
+pub use box_set::*;
 mod line1d;
 pub use line1d::*;
+mod direction;
+pub use direction::*;
+mod vec3d;
+pub use vec3d::*;
+mod box3d;
+pub use box3d::*;
@@ -62,6 +62,73 @@ impl Box2D {
             .cartesian_product(self.lower.y..=self.upper.y)
             .map(move |(x, y)| Vec2D::new(x, y))
     }
+
+    /// True if `lower` is greater than `upper` in any coordinate, e.g. for a box collected from
+    /// no points
+    pub fn is_empty(&self) -> bool {
+        self.lower.x > self.upper.x || self.lower.y > self.upper.y
+    }
+
+    /// Number of points inside, 0 for an empty box
+    pub fn area(&self) -> usize {
+        if self.is_empty() {
+            0
+        } else {
+            self.num_points()
+        }
+    }
+
+    pub fn intersection(&self, other: &Box2D) -> Option<Box2D> {
+        let intersection = Box2D::new(
+            (self.lower.x.max(other.lower.x), self.lower.y.max(other.lower.y)),
+            (self.upper.x.min(other.upper.x), self.upper.y.min(other.upper.y)),
+        );
+        (!intersection.is_empty()).then_some(intersection)
+    }
+
+    pub fn overlaps(&self, other: &Box2D) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// The points of `self` that are not in `other`, as up to 4 disjoint boxes: the full-width
+    /// rows above and below `other`, and the parts left and right of it in between
+    pub fn subtract(&self, other: &Box2D) -> Vec<Box2D> {
+        let Some(cut) = self.intersection(other) else {
+            return if self.is_empty() { Vec::new() } else { vec![self.clone()] };
+        };
+        [
+            Box2D::new(self.lower, (self.upper.x, cut.lower.y - 1)),
+            Box2D::new((self.lower.x, cut.upper.y + 1), self.upper),
+            Box2D::new((self.lower.x, cut.lower.y), (cut.lower.x - 1, cut.upper.y)),
+            Box2D::new((cut.upper.x + 1, cut.lower.y), (self.upper.x, cut.upper.y)),
+        ]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect()
+    }
+
+    /// Splits along the column `at.x` and the row `at.y` into up to 4 disjoint boxes, where `at`
+    /// is the lower corner of the last one. Lines outside of the box don't split it, so e.g.
+    /// `at = (x, lower.y)` only splits into a left and a right part.
+    pub fn split_at(&self, at: impl Into<Vec2D>) -> Vec<Box2D> {
+        let at = at.into();
+        let xs = if at.x > self.lower.x && at.x <= self.upper.x {
+            vec![(self.lower.x, at.x - 1), (at.x, self.upper.x)]
+        } else {
+            vec![(self.lower.x, self.upper.x)]
+        };
+        let ys = if at.y > self.lower.y && at.y <= self.upper.y {
+            vec![(self.lower.y, at.y - 1), (at.y, self.upper.y)]
+        } else {
+            vec![(self.lower.y, self.upper.y)]
+        };
+        ys.into_iter()
+            .cartesian_product(xs)
+            .map(|((lower_y, upper_y), (lower_x, upper_x))| {
+                Box2D::new((lower_x, lower_y), (upper_x, upper_y))
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -125,4 +192,53 @@ mod tests {
             .collect();
         assert_eq!(box2d, Box2D::new(Vec2D::new(0, 0), Vec2D::new(2, 2)));
     }
+
+    #[test]
+    fn test_intersection() {
+        let a = Box2D::new((0, 0), (4, 4));
+        assert_eq!(a.intersection(&Box2D::new((2, -1), (6, 2))), Some(Box2D::new((2, 0), (4, 2))));
+        assert_eq!(a.intersection(&Box2D::new((4, 4), (5, 5))), Some(Box2D::new((4, 4), (4, 4))));
+        assert_eq!(a.intersection(&Box2D::new((5, 0), (6, 4))), None);
+        assert!(a.overlaps(&Box2D::new((1, 1), (2, 2))));
+        assert!(!a.overlaps(&Box2D::new((0, 5), (4, 5))));
+
+        assert_eq!(a.area(), 25);
+        let empty: Box2D = std::iter::empty().collect();
+        assert!(empty.is_empty());
+        assert_eq!(empty.area(), 0);
+    }
+
+    #[test]
+    fn test_subtract() {
+        let a = Box2D::new((0, 0), (4, 4));
+        let check = |other: Box2D, num_parts: usize| {
+            let parts = a.subtract(&other);
+            assert_eq!(parts.len(), num_parts);
+            let points: HashSet<_> = parts.iter().flat_map(|part| part.points_inside()).collect();
+            assert_eq!(points.len(), parts.iter().map(Box2D::area).sum::<usize>());
+            let expected: HashSet<_> = a.points_inside().filter(|p| !other.contains(p)).collect();
+            assert_eq!(points, expected);
+        };
+        check(Box2D::new((1, 1), (2, 3)), 4);
+        check(Box2D::new((-1, -1), (2, 2)), 2);
+        check(Box2D::new((0, 2), (4, 2)), 2);
+        check(Box2D::new((-1, -1), (5, 5)), 0);
+        check(Box2D::new((5, 5), (6, 6)), 1);
+    }
+
+    #[test]
+    fn test_split_at() {
+        let a = Box2D::new((0, 0), (3, 2));
+        assert_eq!(
+            a.split_at((2, 1)),
+            [
+                Box2D::new((0, 0), (1, 0)),
+                Box2D::new((2, 0), (3, 0)),
+                Box2D::new((0, 1), (1, 2)),
+                Box2D::new((2, 1), (3, 2))
+            ]
+        );
+        assert_eq!(a.split_at((2, 0)), [Box2D::new((0, 0), (1, 2)), Box2D::new((2, 0), (3, 2))]);
+        assert_eq!(a.split_at((10, -10)), std::slice::from_ref(&a));
+    }
 }
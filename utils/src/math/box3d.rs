@@ -0,0 +1,126 @@
+use std::str::FromStr;
+
+use itertools::Itertools;
+
+use crate::extract_numbers;
+use crate::math::Vec3D;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Box3D {
+    pub lower: Vec3D,
+    pub upper: Vec3D,
+}
+
+impl From<(Vec3D, Vec3D)> for Box3D {
+    fn from((lower, upper): (Vec3D, Vec3D)) -> Self {
+        Self { lower, upper }
+    }
+}
+
+impl FromIterator<Vec3D> for Box3D {
+    fn from_iter<I: IntoIterator<Item = Vec3D>>(iter: I) -> Self {
+        let mut box3d = Self::new((i64::MAX, i64::MAX, i64::MAX), (i64::MIN, i64::MIN, i64::MIN));
+        for point in iter {
+            box3d.extend(point);
+        }
+        box3d
+    }
+}
+
+/// Parses two corners from the first six numbers in the string, e.g. `1,0,1~1,2,1`. The corners
+/// may be given in any order.
+impl FromStr for Box3D {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (x1, y1, z1, x2, y2, z2) = extract_numbers(s)
+            .next_tuple()
+            .ok_or_else(|| format!("Expected six numbers in {s:?}"))?;
+        Ok([Vec3D::new(x1, y1, z1), Vec3D::new(x2, y2, z2)].into_iter().collect())
+    }
+}
+
+impl Box3D {
+    pub fn new(lower: impl Into<Vec3D>, upper: impl Into<Vec3D>) -> Self {
+        Self { lower: lower.into(), upper: upper.into() }
+    }
+
+    pub fn lower(&self) -> Vec3D {
+        self.lower
+    }
+
+    pub fn upper(&self) -> Vec3D {
+        self.upper
+    }
+
+    pub fn extend_border(&self, amount: i64) -> Self {
+        Self::new(self.lower - (amount, amount, amount), self.upper + (amount, amount, amount))
+    }
+
+    pub fn contains(&self, point: &Vec3D) -> bool {
+        point.inside_box(self.lower, self.upper)
+    }
+
+    pub fn extend(&mut self, point: impl Into<Vec3D>) {
+        let point = point.into();
+        self.lower = Vec3D::new(
+            self.lower.x.min(point.x),
+            self.lower.y.min(point.y),
+            self.lower.z.min(point.z),
+        );
+        self.upper = Vec3D::new(
+            self.upper.x.max(point.x),
+            self.upper.y.max(point.y),
+            self.upper.z.max(point.z),
+        );
+    }
+
+    pub fn num_points(&self) -> usize {
+        let size = self.upper - self.lower + (1, 1, 1);
+        (size.x * size.y * size.z) as usize
+    }
+
+    pub fn points_inside(&self) -> impl Iterator<Item = Vec3D> + '_ {
+        (self.lower.x..=self.upper.x)
+            .cartesian_product(self.lower.y..=self.upper.y)
+            .cartesian_product(self.lower.z..=self.upper.z)
+            .map(|((x, y), z)| Vec3D::new(x, y, z))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn test_contains() {
+        let box3d = Box3D::new((-1, 0, 0), (1, 2, 0));
+        assert!(box3d.contains(&Vec3D::new(-1, 0, 0)));
+        assert!(box3d.contains(&Vec3D::new(1, 2, 0)));
+        assert!(!box3d.contains(&Vec3D::new(0, 0, 1)));
+        assert!(!box3d.contains(&Vec3D::new(2, 0, 0)));
+
+        assert_eq!(box3d.num_points(), 9);
+        let points: HashSet<_> = box3d.points_inside().collect();
+        assert_eq!(points.len(), 9);
+        assert!(points.iter().all(|p| box3d.contains(p)));
+        assert_eq!(box3d.extend_border(1).num_points(), 5 * 5 * 3);
+    }
+
+    #[test]
+    fn test_extend() {
+        let mut box3d: Box3D = [Vec3D::new(1, 1, 1)].into_iter().collect();
+        assert_eq!(box3d, Box3D::new((1, 1, 1), (1, 1, 1)));
+        box3d.extend((3, 0, 2));
+        assert_eq!(box3d, Box3D::new((1, 0, 1), (3, 1, 2)));
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("1,0,1~1,2,1".parse(), Ok(Box3D::new((1, 0, 1), (1, 2, 1))));
+        assert_eq!("2,2,2~0,2,-1".parse(), Ok(Box3D::new((0, 2, -1), (2, 2, 2))));
+        assert!("1,0,1~1,2".parse::<Box3D>().is_err());
+    }
+}
@@ -0,0 +1,101 @@
+use crate::math::{Box2D, Vec2D};
+
+/// Union of boxes, stored as disjoint boxes so that covered points can be counted without
+/// enumerating them
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BoxSet {
+    boxes: Vec<Box2D>,
+}
+
+impl BoxSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the parts of `new` that are not covered yet
+    pub fn insert(&mut self, new: Box2D) {
+        if new.is_empty() {
+            return;
+        }
+        let mut parts = vec![new];
+        for existing in &self.boxes {
+            parts = parts.iter().flat_map(|part| part.subtract(existing)).collect();
+        }
+        self.boxes.extend(parts);
+    }
+
+    /// Removes all points inside `removed`
+    pub fn remove(&mut self, removed: &Box2D) {
+        self.boxes = self.boxes.iter().flat_map(|existing| existing.subtract(removed)).collect();
+    }
+
+    pub fn contains(&self, point: &Vec2D) -> bool {
+        self.boxes.iter().any(|b| b.contains(point))
+    }
+
+    /// Number of covered points
+    pub fn area(&self) -> usize {
+        self.boxes.iter().map(Box2D::area).sum()
+    }
+
+    /// The covered points inside `region`
+    pub fn area_within(&self, region: &Box2D) -> usize {
+        self.boxes.iter().filter_map(|b| b.intersection(region)).map(|b| b.area()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.boxes.is_empty()
+    }
+
+    /// The disjoint boxes making up the union, in no particular order
+    pub fn boxes(&self) -> &[Box2D] {
+        &self.boxes
+    }
+}
+
+impl FromIterator<Box2D> for BoxSet {
+    fn from_iter<I: IntoIterator<Item = Box2D>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for b in iter {
+            set.insert(b);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn union() {
+        let boxes =
+            [Box2D::new((0, 0), (3, 3)), Box2D::new((2, 2), (5, 4)), Box2D::new((1, -2), (1, 6))];
+        let mut set: BoxSet = boxes.iter().cloned().collect();
+        let points: HashSet<_> = boxes.iter().flat_map(|b| b.points_inside()).collect();
+        assert_eq!(set.area(), points.len());
+        assert!(set.contains(&Vec2D::new(5, 4)));
+        assert!(!set.contains(&Vec2D::new(5, 5)));
+        assert_eq!(set.area_within(&Box2D::new((0, 0), (1, 1))), 4);
+
+        set.remove(&Box2D::new((1, 0), (4, 6)));
+        let points: HashSet<_> =
+            points.into_iter().filter(|p| !p.inside_box((1, 0), (4, 6))).collect();
+        assert_eq!(set.area(), points.len());
+        assert!(set.boxes().iter().flat_map(|b| b.points_inside()).all(|p| points.contains(&p)));
+
+        set.remove(&Box2D::new((-10, -10), (10, 10)));
+        assert!(set.is_empty());
+        assert_eq!(set.area(), 0);
+    }
+
+    #[test]
+    fn empty_boxes_are_ignored() {
+        let set: BoxSet =
+            [Box2D::new((1, 1), (0, 0)), Box2D::new((0, 3), (2, 2))].into_iter().collect();
+        assert!(set.is_empty());
+        assert_eq!(set.boxes(), []);
+    }
+}
@@ -0,0 +1,200 @@
+use std::str::FromStr;
+
+use super::Vec2D;
+
+/// Compass direction on a grid where y grows downwards, i.e. north is `(0, -1)`
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+use Direction::*;
+
+impl Direction {
+    /// The 4 orthogonal directions, clockwise starting with north
+    pub const ORTHOGONAL: [Direction; 4] = [North, East, South, West];
+
+    /// The 4 diagonal directions, clockwise starting with north-east
+    pub const DIAGONAL: [Direction; 4] = [NorthEast, SouthEast, SouthWest, NorthWest];
+
+    /// All 8 directions, clockwise starting with north
+    pub const ALL: [Direction; 8] =
+        [North, NorthEast, East, SouthEast, South, SouthWest, West, NorthWest];
+
+    pub fn is_diagonal(&self) -> bool {
+        (*self as u8) % 2 == 1
+    }
+
+    /// Rotates clockwise by `eighths` of a full turn
+    fn rotated(&self, eighths: u8) -> Self {
+        Self::ALL[((*self as u8 + eighths) % 8) as usize]
+    }
+
+    /// 90° counterclockwise
+    pub fn turn_left(&self) -> Self {
+        self.rotated(6)
+    }
+
+    /// 90° clockwise
+    pub fn turn_right(&self) -> Self {
+        self.rotated(2)
+    }
+
+    /// 45° counterclockwise
+    pub fn turn_left_45(&self) -> Self {
+        self.rotated(7)
+    }
+
+    /// 45° clockwise
+    pub fn turn_right_45(&self) -> Self {
+        self.rotated(1)
+    }
+
+    pub fn opposite(&self) -> Self {
+        self.rotated(4)
+    }
+
+    pub fn to_vec(&self) -> Vec2D {
+        match self {
+            North => Vec2D::new(0, -1),
+            NorthEast => Vec2D::new(1, -1),
+            East => Vec2D::new(1, 0),
+            SouthEast => Vec2D::new(1, 1),
+            South => Vec2D::new(0, 1),
+            SouthWest => Vec2D::new(-1, 1),
+            West => Vec2D::new(-1, 0),
+            NorthWest => Vec2D::new(-1, -1),
+        }
+    }
+
+    /// The direction of a unit step like `(1, 0)` or `(-1, 1)`
+    pub fn from_vec(vec: Vec2D) -> Option<Self> {
+        Self::ALL.into_iter().find(|dir| dir.to_vec() == vec)
+    }
+
+    /// Parses arrows (`^>v<`), compass letters (`NESW`) and `UDLR`
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            '^' | 'N' | 'U' => Some(North),
+            '>' | 'E' | 'R' => Some(East),
+            'v' | 'S' | 'D' => Some(South),
+            '<' | 'W' | 'L' => Some(West),
+            _ => None,
+        }
+    }
+
+    /// Arrow for orthogonal directions, panics for diagonal ones
+    pub fn to_arrow(&self) -> char {
+        match self {
+            North => '^',
+            East => '>',
+            South => 'v',
+            West => '<',
+            _ => panic!("{self:?} has no arrow"),
+        }
+    }
+}
+
+impl From<Direction> for Vec2D {
+    fn from(dir: Direction) -> Self {
+        dir.to_vec()
+    }
+}
+
+impl TryFrom<Vec2D> for Direction {
+    type Error = String;
+
+    fn try_from(vec: Vec2D) -> Result<Self, Self::Error> {
+        Self::from_vec(vec).ok_or_else(|| format!("{vec} is not a unit step"))
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = String;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        Self::from_char(c).ok_or_else(|| format!("'{c}' is not a direction"))
+    }
+}
+
+/// Parses a single direction character (see [`Direction::from_char`]) or two of them for
+/// diagonals, e.g. `NE` or `UL`
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || format!("'{s}' is not a direction");
+        let dirs: Vec<_> =
+            s.chars().map(Direction::from_char).collect::<Option<_>>().ok_or_else(err)?;
+        match dirs[..] {
+            [dir] => Ok(dir),
+            [a, b] => Self::from_vec(a.to_vec() + b.to_vec())
+                .filter(|dir| dir.is_diagonal())
+                .ok_or_else(err),
+            _ => Err(err()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn turning() {
+        assert_eq!(North.turn_right(), East);
+        assert_eq!(North.turn_left(), West);
+        assert_eq!(West.turn_right(), North);
+        assert_eq!(NorthEast.turn_right(), SouthEast);
+        assert_eq!(North.turn_right_45(), NorthEast);
+        assert_eq!(North.turn_left_45(), NorthWest);
+        assert_eq!(SouthWest.opposite(), NorthEast);
+
+        for dir in Direction::ALL {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(dir.opposite().to_vec(), dir.to_vec() * -1);
+            assert_eq!(dir.turn_right().to_vec(), dir.to_vec().rotated_right());
+        }
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Vec2D::from(North), Vec2D::new(0, -1));
+        assert_eq!(Vec2D::new(3, 3) + SouthWest, Vec2D::new(2, 4));
+        assert_eq!(Direction::try_from(Vec2D::new(-1, -1)), Ok(NorthWest));
+        assert!(Direction::try_from(Vec2D::new(2, 0)).is_err());
+
+        assert_eq!(
+            "^>v<".chars().map(|c| Direction::try_from(c).unwrap()).collect::<Vec<_>>(),
+            Direction::ORTHOGONAL
+        );
+        assert_eq!(
+            "NESW".chars().filter_map(Direction::from_char).collect::<Vec<_>>(),
+            Direction::ORTHOGONAL
+        );
+        assert_eq!(
+            "URDL".chars().filter_map(Direction::from_char).collect::<Vec<_>>(),
+            Direction::ORTHOGONAL
+        );
+        assert_eq!("SE".parse(), Ok(SouthEast));
+        assert_eq!("UL".parse(), Ok(NorthWest));
+        assert_eq!("W".parse(), Ok(West));
+        assert!("NS".parse::<Direction>().is_err());
+        assert!("X".parse::<Direction>().is_err());
+        assert_eq!(East.to_arrow(), '>');
+
+        assert_eq!(Direction::ALL.iter().filter(|dir| dir.is_diagonal()).count(), 4);
+        assert_eq!(HashSet::from(Direction::ALL).len(), 8);
+        assert_eq!(size_of::<Direction>(), 1);
+    }
+}
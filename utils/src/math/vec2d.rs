@@ -1,134 +1,334 @@
+use std::cmp::Ordering;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use num::traits::Euclid;
+use num::{FromPrimitive, Signed, ToPrimitive, Zero};
 use parse_display::FromStr;
 
-#[derive(Copy, Clone, Debug, Eq, Hash, FromStr, PartialOrd, Ord, derive_more::Display)]
+/// The coordinates default to `i64`, but any `num` scalar like `i32`, `i128`, `f64` or
+/// [`num::Rational64`] works
+#[derive(Copy, Clone, Debug, Hash, FromStr, derive_more::Display)]
 #[display("({x}, {y})")]
 // Parses [1, 2] or (1, 2) or {1, 2}
 #[from_str(regex = r"[\[\(\{](?<x>-?\d+),\s*(?<y>-?\d+)[\]\)\}]")]
-pub struct Vec2D {
-    pub x: i64,
-    pub y: i64,
+pub struct Vec2D<T = i64> {
+    pub x: T,
+    pub y: T,
 }
 
-impl<T> PartialEq<T> for Vec2D
+impl<S, T> PartialEq<T> for Vec2D<S>
 where
-    T: Into<Vec2D> + Copy,
+    S: PartialEq,
+    T: Into<Vec2D<S>> + Copy,
 {
     fn eq(&self, other: &T) -> bool {
-        let other: Vec2D = (*other).into();
+        let other: Vec2D<S> = (*other).into();
         self.x == other.x && self.y == other.y
     }
 }
 
+impl<S: Eq + Copy> Eq for Vec2D<S> {}
+
+/// Compares `x` first, then `y`
+impl<S: PartialOrd + Copy> PartialOrd for Vec2D<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.x.partial_cmp(&other.x)? {
+            Ordering::Equal => self.y.partial_cmp(&other.y),
+            ordering => Some(ordering),
+        }
+    }
+}
+
+impl<S: Ord + Copy> Ord for Vec2D<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.x.cmp(&other.x).then_with(|| self.y.cmp(&other.y))
+    }
+}
+
+/// Scalars that have a [`Vec2D::manhattan_dist`]. Integer distances are unsigned so that they can
+/// be used as indices and costs directly.
+pub trait Distance: Signed + Copy {
+    type Dist;
+
+    fn distance(self) -> Self::Dist;
+}
+
+macro_rules! impl_distance {
+    ($abs:ident: $($t:ty => $output:ty),*) => {
+        $(
+            impl Distance for $t {
+                type Dist = $output;
+
+                fn distance(self) -> Self::Dist {
+                    self.$abs() as $output
+                }
+            }
+        )*
+    };
+}
+impl_distance!(
+    unsigned_abs: i8 => usize, i16 => usize, i32 => usize, i64 => usize, isize => usize,
+    i128 => u128
+);
+impl_distance!(abs: f32 => f32, f64 => f64);
+
+impl<T: Copy + num::Integer + Signed> Distance for num::rational::Ratio<T> {
+    type Dist = Self;
+
+    fn distance(self) -> Self::Dist {
+        self.abs()
+    }
+}
+
 impl Vec2D {
+    /// Only for the default `i64` coordinates, so that e.g. `Vec2D::new(1, 2)` doesn't need type
+    /// annotations. Use [`Vec2D::from_xy`] for other scalar types.
     pub fn new(x: i64, y: i64) -> Self {
         Self { x, y }
     }
+}
+
+impl<T> Vec2D<T> {
+    pub fn from_xy(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
 
-    pub fn xy_tuple(&self) -> (i64, i64) {
+impl<T: Copy> Vec2D<T> {
+    pub fn xy_tuple(&self) -> (T, T) {
         (self.x, self.y)
     }
 
+    pub fn flipped(&self) -> Self {
+        Self::from_xy(self.y, self.x)
+    }
+
+    /// Multiplies by a factor of the same scalar type, e.g. `0.5` for an `f64` vector. The `*`
+    /// operator only takes integer factors.
+    pub fn scaled(self, factor: T) -> Self
+    where
+        T: Mul<Output = T>,
+    {
+        Self::from_xy(self.x * factor, self.y * factor)
+    }
+
+    /// Converts the coordinates to another scalar type. Whole numbers are converted exactly,
+    /// everything else goes through `f64`. Panics if a fractional coordinate would be truncated.
+    pub fn cast<U: FromPrimitive + ToPrimitive>(&self) -> Vec2D<U>
+    where
+        T: ToPrimitive + FromPrimitive + PartialEq,
+    {
+        Vec2D::from_xy(convert(self.x), convert(self.y))
+    }
+}
+
+impl<T: Zero + Copy> Vec2D<T> {
     pub fn zero() -> Self {
-        Self::new(0, 0)
+        Self::from_xy(T::zero(), T::zero())
     }
 
     pub fn is_zero(&self) -> bool {
-        *self == Self::zero()
+        self.x.is_zero() && self.y.is_zero()
     }
 
-    pub fn xaxis(x: i64) -> Self {
-        Self { x, y: 0 }
+    pub fn xaxis(x: T) -> Self {
+        Self::from_xy(x, T::zero())
     }
 
-    pub fn yaxis(y: i64) -> Self {
-        Self { x: 0, y }
+    pub fn yaxis(y: T) -> Self {
+        Self::from_xy(T::zero(), y)
     }
+}
 
+impl<T: Signed + Copy> Vec2D<T> {
     pub fn abs(&self) -> Self {
-        Self::new(self.x.abs(), self.y.abs())
-    }
-
-    pub fn flipped(&self) -> Self {
-        Self::new(self.y, self.x)
+        Self::from_xy(self.x.abs(), self.y.abs())
     }
 
     pub fn rotated_right(&self) -> Self {
-        Self::new(-self.y, self.x)
+        Self::from_xy(-self.y, self.x)
     }
 
     pub fn rotated_left(&self) -> Self {
-        Self::new(self.y, -self.x)
+        Self::from_xy(self.y, -self.x)
     }
 
-    pub fn manhattan_dist(&self) -> usize {
-        (self.x.abs() + self.y.abs()) as usize
+    /// Offsets to the 4 diagonal neighbors, followed by the 4 orthogonal ones
+    fn neighbor_offsets() -> [Self; 8] {
+        let (zero, one) = (T::zero(), T::one());
+        [
+            Self::from_xy(one, one),
+            Self::from_xy(-one, one),
+            Self::from_xy(one, -one),
+            Self::from_xy(-one, -one),
+            Self::from_xy(one, zero),
+            Self::from_xy(-one, zero),
+            Self::from_xy(zero, one),
+            Self::from_xy(zero, -one),
+        ]
     }
 
+    pub fn diagonal_neighbors(&self) -> impl Iterator<Item = Self> + '_ {
+        Self::neighbor_offsets().into_iter().take(4).map(move |dir| *self + dir)
+    }
+
+    pub fn orthogonal_neighbors(&self) -> impl Iterator<Item = Self> + '_ {
+        Self::neighbor_offsets().into_iter().skip(4).map(move |dir| *self + dir)
+    }
+
+    pub fn all_neighbors(&self) -> impl Iterator<Item = Self> + '_ {
+        self.diagonal_neighbors().chain(self.orthogonal_neighbors())
+    }
+}
+
+impl<T: Distance> Vec2D<T> {
+    /// Unsigned for integer coordinates, e.g. `usize` for `i64`
+    pub fn manhattan_dist(&self) -> T::Dist {
+        (self.x.abs() + self.y.abs()).distance()
+    }
+}
+
+impl<T: PartialOrd + Zero + Copy> Vec2D<T> {
     pub fn both_coordinates_positive(&self) -> bool {
-        self.x >= 0 && self.y >= 0
+        self.x >= T::zero() && self.y >= T::zero()
     }
+}
 
-    pub fn inside_box(&self, lower: impl Into<Vec2D>, upper: impl Into<Vec2D>) -> bool {
+impl<T: PartialOrd + Copy> Vec2D<T> {
+    pub fn inside_box(&self, lower: impl Into<Vec2D<T>>, upper: impl Into<Vec2D<T>>) -> bool {
         let (lower, upper) = (lower.into(), upper.into());
         self.x >= lower.x && self.y >= lower.y && self.x <= upper.x && self.y <= upper.y
     }
+}
 
-    pub fn diagonal_neighbors(&self) -> impl Iterator<Item = Vec2D> + '_ {
-        [Vec2D { x: 1, y: 1 }, Vec2D { x: -1, y: 1 }, Vec2D { x: 1, y: -1 }, Vec2D { x: -1, y: -1 }]
-            .iter()
-            .map(move |&dir| *self + dir)
+impl<T: PartialOrd + Zero + Copy> Vec2D<T> {
+    /// Whether both coordinates are in `0..size`, e.g. `pos.inside_size((num_cols, num_rows))`
+    pub fn inside_size(&self, size: impl Into<Vec2D<T>>) -> bool {
+        let size = size.into();
+        self.both_coordinates_positive() && self.x < size.x && self.y < size.y
     }
+}
 
-    pub fn orthogonal_neighbors(&self) -> impl Iterator<Item = Vec2D> + '_ {
-        [Vec2D { x: 1, y: 0 }, Vec2D { x: -1, y: 0 }, Vec2D { x: 0, y: 1 }, Vec2D { x: 0, y: -1 }]
-            .iter()
-            .map(move |&dir| *self + dir)
+impl<T: Euclid + Copy> Vec2D<T> {
+    /// Componentwise euclidean remainder, i.e. wraps the position into the box from `(0, 0)`
+    /// (inclusive) to `bounds` (exclusive)
+    pub fn rem_euclid(&self, bounds: impl Into<Vec2D<T>>) -> Self {
+        let bounds = bounds.into();
+        Self::from_xy(self.x.rem_euclid(&bounds.x), self.y.rem_euclid(&bounds.y))
     }
+}
 
-    pub fn all_neighbors(&self) -> impl Iterator<Item = Vec2D> + '_ {
-        self.diagonal_neighbors().chain(self.orthogonal_neighbors())
+impl Vec2D {
+    /// All points on the line from `self` to `to` (both inclusive), using Bresenham's algorithm
+    pub fn line_to(&self, to: impl Into<Vec2D>) -> impl Iterator<Item = Vec2D> {
+        let to = to.into();
+        let (dx, dy) = ((to.x - self.x).abs(), -(to.y - self.y).abs());
+        let step = Vec2D::new((to.x - self.x).signum(), (to.y - self.y).signum());
+        let mut error = dx + dy;
+        let mut next = Some(*self);
+
+        std::iter::from_fn(move || {
+            let pos = next?;
+            next = (pos != to).then(|| {
+                let (mut next, doubled_error) = (pos, 2 * error);
+                if doubled_error >= dy {
+                    error += dy;
+                    next.x += step.x;
+                }
+                if doubled_error <= dx {
+                    error += dx;
+                    next.y += step.y;
+                }
+                next
+            });
+            Some(pos)
+        })
+    }
+}
+
+/// Converts whole numbers exactly and everything else through `f64`. Panics if the value doesn't
+/// fit, or if it has a fractional part that an integer `U` would truncate.
+fn convert<U, T>(value: T) -> U
+where
+    U: FromPrimitive + ToPrimitive,
+    T: ToPrimitive + FromPrimitive + PartialEq + Copy,
+{
+    if let Some(converted) = convert_whole(value) {
+        return converted;
     }
+    let float = value.to_f64().expect("Coordinate out of bounds");
+    let truncates = U::from_f64(0.5).and_then(|half| half.to_f64()) == Some(0.0);
+    assert!(!truncates || float.fract() == 0.0, "Coordinate {float} is not an integer");
+    U::from_f64(float).expect("Coordinate out of bounds")
+}
+
+/// Like [`convert_integer`], but also accepts non-integer types like floats, as long as the value
+/// survives the round trip through an integer unchanged. For integer types, this never touches a
+/// float.
+fn convert_whole<U: FromPrimitive, T: ToPrimitive + FromPrimitive + PartialEq + Copy>(
+    value: T,
+) -> Option<U> {
+    let exact_i64 = value.to_i64().filter(|&i| T::from_i64(i) == Some(value));
+    let exact_i128 = || value.to_i128().filter(|&i| T::from_i128(i) == Some(value));
+    exact_i64.and_then(U::from_i64).or_else(|| exact_i128().and_then(U::from_i128))
+}
+
+/// Fast path without any float conversion, only valid if `value` is a whole number
+fn convert_integer<U: FromPrimitive, T: ToPrimitive>(value: T) -> Option<U> {
+    value.to_i64().and_then(U::from_i64).or_else(|| value.to_i128().and_then(U::from_i128))
 }
 
 /// This would of course be horrible in productions, but it make the Advent of Code solutions much
-/// more readable. We assume that we never need any positions whose coordinates are out of i64
+/// more readable. We assume that we never need any positions whose coordinates are out of
 /// bounds.
-impl<T, U> From<(T, U)> for Vec2D
+impl<S, T, U> From<(T, U)> for Vec2D<S>
 where
-    T: num::ToPrimitive,
-    U: num::ToPrimitive,
+    S: FromPrimitive + ToPrimitive,
+    T: ToPrimitive + FromPrimitive + PartialEq + Copy,
+    U: ToPrimitive + FromPrimitive + PartialEq + Copy,
 {
     fn from((x, y): (T, U)) -> Self {
-        Self::new(x.to_i64().unwrap(), y.to_i64().unwrap())
+        Self::from_xy(convert(x), convert(y))
     }
 }
 
-impl<T, U> From<&(T, U)> for Vec2D
+impl<S, T, U> From<&(T, U)> for Vec2D<S>
 where
-    T: num::ToPrimitive + Copy,
-    U: num::ToPrimitive + Copy,
+    S: FromPrimitive + ToPrimitive,
+    T: ToPrimitive + FromPrimitive + PartialEq + Copy,
+    U: ToPrimitive + FromPrimitive + PartialEq + Copy,
 {
     fn from((x, y): &(T, U)) -> Self {
         Self::from((*x, *y))
     }
 }
 
+/// Makes it possible to pass e.g. `map.keys()` wherever `impl Into<Vec2D>` items are expected
+impl<T: Copy> From<&Vec2D<T>> for Vec2D<T> {
+    fn from(pos: &Vec2D<T>) -> Self {
+        *pos
+    }
+}
+
 /// Again, horrible in production, but nice for AoC.
 /// This way we don't need to manually convert before multiplying.
-impl<T> std::ops::Mul<T> for Vec2D
+impl<S, T> Mul<T> for Vec2D<S>
 where
-    T: num::ToPrimitive + num::Integer,
+    S: FromPrimitive + Mul<Output = S> + Copy,
+    T: ToPrimitive + num::Integer,
 {
-    type Output = Vec2D;
+    type Output = Vec2D<S>;
 
     fn mul(self, rhs: T) -> Self::Output {
-        Vec2D::new(self.x * rhs.to_i64().unwrap(), self.y * rhs.to_i64().unwrap())
+        let rhs: S = convert_integer(rhs).expect("Factor out of bounds");
+        Vec2D::from_xy(self.x * rhs, self.y * rhs)
     }
 }
 
-impl<T> std::ops::MulAssign<T> for Vec2D
+impl<S, T> std::ops::MulAssign<T> for Vec2D<S>
 where
-    T: num::ToPrimitive + num::Integer,
+    S: FromPrimitive + Mul<Output = S> + Copy,
+    T: ToPrimitive + num::Integer,
 {
     fn mul_assign(&mut self, rhs: T) {
         *self = *self * rhs;
@@ -140,10 +340,13 @@ where
 macro_rules! impl_left_mul {
     ($($t:ty),*) => {
         $(
-            impl std::ops::Mul<Vec2D> for $t {
-                type Output = Vec2D;
+            impl<S> Mul<Vec2D<S>> for $t
+            where
+                S: FromPrimitive + Mul<Output = S> + Copy,
+            {
+                type Output = Vec2D<S>;
 
-                fn mul(self, rhs: Vec2D) -> Self::Output {
+                fn mul(self, rhs: Vec2D<S>) -> Self::Output {
                     rhs * self
                 }
             }
@@ -152,52 +355,66 @@ macro_rules! impl_left_mul {
 }
 impl_left_mul!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
 
-impl<T> std::ops::Add<T> for Vec2D
+impl<S, T> Add<T> for Vec2D<S>
 where
-    T: Into<Vec2D>,
+    S: Add<Output = S>,
+    T: Into<Vec2D<S>>,
 {
-    type Output = Vec2D;
+    type Output = Vec2D<S>;
 
     fn add(self, rhs: T) -> Self::Output {
         let rhs = rhs.into();
-        Vec2D::new(self.x + rhs.x, self.y + rhs.y)
+        Vec2D::from_xy(self.x + rhs.x, self.y + rhs.y)
     }
 }
 
-impl<T> std::ops::AddAssign<T> for Vec2D
+impl<S, T> std::ops::AddAssign<T> for Vec2D<S>
 where
-    T: Into<Vec2D>,
+    S: Add<Output = S> + Copy,
+    T: Into<Vec2D<S>>,
 {
     fn add_assign(&mut self, rhs: T) {
         *self = *self + rhs;
     }
 }
 
-impl<T> std::ops::Sub<T> for Vec2D
+impl<S, T> Sub<T> for Vec2D<S>
 where
-    T: Into<Vec2D>,
+    S: Sub<Output = S>,
+    T: Into<Vec2D<S>>,
 {
-    type Output = Vec2D;
+    type Output = Vec2D<S>;
 
     fn sub(self, rhs: T) -> Self::Output {
         let rhs = rhs.into();
-        Vec2D::new(self.x - rhs.x, self.y - rhs.y)
+        Vec2D::from_xy(self.x - rhs.x, self.y - rhs.y)
     }
 }
 
-impl<T> std::ops::SubAssign<T> for Vec2D
+impl<S, T> std::ops::SubAssign<T> for Vec2D<S>
 where
-    T: Into<Vec2D>,
+    S: Sub<Output = S> + Copy,
+    T: Into<Vec2D<S>>,
 {
     fn sub_assign(&mut self, rhs: T) {
         *self = *self - rhs;
     }
 }
 
+impl<S: Neg<Output = S>> Neg for Vec2D<S> {
+    type Output = Vec2D<S>;
+
+    fn neg(self) -> Self::Output {
+        Vec2D::from_xy(-self.x, -self.y)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
 
+    use itertools::Itertools;
+
     use super::*;
 
     #[test]
@@ -242,6 +459,23 @@ mod tests {
         assert_eq!(Vec2D::new(1, 2).rotated_left(), Vec2D::new(2, -1));
     }
 
+    #[test]
+    fn rem_euclid() {
+        assert_eq!(Vec2D::new(5, -1).rem_euclid((3, 4)), Vec2D::new(2, 3));
+        assert_eq!(Vec2D::new(-7, 8).rem_euclid((7, 4)), Vec2D::new(0, 0));
+    }
+
+    #[test]
+    fn line_to() {
+        let line = |from: (i64, i64), to: (i64, i64)| Vec2D::from(from).line_to(to).collect_vec();
+        assert_eq!(line((0, 0), (0, 0)), [(0, 0)]);
+        assert_eq!(line((0, 0), (3, 0)), [(0, 0), (1, 0), (2, 0), (3, 0)]);
+        assert_eq!(line((1, 1), (-1, -1)), [(1, 1), (0, 0), (-1, -1)]);
+        assert_eq!(line((0, 0), (4, 2)), [(0, 0), (1, 1), (2, 1), (3, 2), (4, 2)]);
+        assert_eq!(line((0, 0), (-1, 3)), [(0, 0), (0, 1), (-1, 2), (-1, 3)]);
+        assert_eq!(line((2, 5), (7, -3)).len(), 9);
+    }
+
     #[test]
     fn manhattan_dist() {
         assert_eq!(Vec2D::new(1, 2).manhattan_dist(), 3);
@@ -256,6 +490,10 @@ mod tests {
         assert!(!Vec2D::new(1, 2).inside_box((0, 0), (1, 1)));
         assert!(!Vec2D::new(1, 2).inside_box((2, 3), (3, 4)));
 
+        assert!(Vec2D::new(1, 2).inside_size((2_usize, 3_usize)));
+        assert!(!Vec2D::new(2, 2).inside_size((2_usize, 3_usize)));
+        assert!(!Vec2D::new(-1, 0).inside_size((2_usize, 3_usize)));
+
         assert!(Vec2D::new(-1, -2).inside_box((-2, -3), (0, 0)));
         assert!(Vec2D::new(-1, -2).inside_box((-1, -2), (-1, -2)));
         assert!(!Vec2D::new(-1, -2).inside_box((0, 0), (1, 1)));
@@ -299,4 +537,43 @@ mod tests {
             Vec2D::new(1, 2).all_neighbors().collect(),
         );
     }
+
+    #[test]
+    fn generic_scalars() {
+        let vec: Vec2D<i32> = Vec2D::from_xy(1, -2) + (3, 4);
+        assert_eq!(vec, (4, 2));
+        assert_eq!((vec * 2).manhattan_dist(), 12usize);
+        assert_eq!(vec.orthogonal_neighbors().count(), 4);
+
+        let vec = Vec2D::<i128>::from((1_i64 << 40, -1)) * 4;
+        assert_eq!(vec.manhattan_dist(), (1_u128 << 42) + 4);
+
+        let vec = Vec2D::from_xy(1.5, -0.5) - (1, 1);
+        assert_eq!(vec, Vec2D::from_xy(0.5, -1.5));
+        assert_eq!(vec.manhattan_dist(), 2.0);
+        assert_eq!(vec.rotated_right(), Vec2D::from_xy(1.5, 0.5));
+        assert_eq!((vec * 2).cast::<i64>(), Vec2D::new(1, -3));
+        assert_eq!(vec.cast::<f32>(), Vec2D::from_xy(0.5, -1.5));
+        assert!(vec.all_neighbors().any(|n| n == (1.5, -0.5)));
+        assert_eq!(Vec2D::<f64>::from((0.5, -1.5)), Vec2D::from_xy(0.5, -1.5));
+        assert_eq!(Vec2D::<i128>::from((u64::MAX, 1.0)), Vec2D::from_xy(u64::MAX.into(), 1));
+        assert_eq!(Vec2D::from_xy(1.0, 2.0).scaled(0.5), Vec2D::from_xy(0.5, 1.0));
+
+        let half = num::Rational64::new(1, 2);
+        let vec = Vec2D::from_xy(half, -half) * 3;
+        assert_eq!(vec.manhattan_dist(), num::Rational64::from_integer(3));
+        assert_eq!(
+            vec.scaled(half),
+            Vec2D::from_xy(num::Rational64::new(3, 4), -num::Rational64::new(3, 4))
+        );
+        assert_eq!(-vec + (2, 1), Vec2D::from_xy(half, num::Rational64::new(5, 2)));
+        assert_eq!(Vec2D::new(2, 3).cast::<f64>(), Vec2D::from_xy(2.0, 3.0));
+        assert_eq!(Vec2D::from_xy(1_u128 << 100, 1).cast::<f64>().x, 2.0_f64.powi(100));
+    }
+
+    #[test]
+    #[should_panic(expected = "not an integer")]
+    fn cast_does_not_truncate() {
+        Vec2D::from_xy(0.5, 1.0).cast::<i64>();
+    }
 }
@@ -0,0 +1,278 @@
+use std::str::FromStr;
+
+use itertools::Itertools;
+
+use crate::extract_numbers;
+
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialOrd, Ord, derive_more::Display)]
+#[display("({x}, {y}, {z})")]
+pub struct Vec3D {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl<T> PartialEq<T> for Vec3D
+where
+    T: Into<Vec3D> + Copy,
+{
+    fn eq(&self, other: &T) -> bool {
+        let other: Vec3D = (*other).into();
+        self.x == other.x && self.y == other.y && self.z == other.z
+    }
+}
+
+/// Offsets to the 26 surrounding positions, the first 6 share a face and the first 18 share at
+/// least an edge with the center
+const NEIGHBOR_OFFSETS: [(i64, i64, i64); 26] = [
+    (1, 0, 0),
+    (-1, 0, 0),
+    (0, 1, 0),
+    (0, -1, 0),
+    (0, 0, 1),
+    (0, 0, -1),
+    (1, 1, 0),
+    (1, -1, 0),
+    (-1, 1, 0),
+    (-1, -1, 0),
+    (1, 0, 1),
+    (1, 0, -1),
+    (-1, 0, 1),
+    (-1, 0, -1),
+    (0, 1, 1),
+    (0, 1, -1),
+    (0, -1, 1),
+    (0, -1, -1),
+    (1, 1, 1),
+    (1, 1, -1),
+    (1, -1, 1),
+    (1, -1, -1),
+    (-1, 1, 1),
+    (-1, 1, -1),
+    (-1, -1, 1),
+    (-1, -1, -1),
+];
+
+impl Vec3D {
+    pub fn new(x: i64, y: i64, z: i64) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn xyz_tuple(&self) -> (i64, i64, i64) {
+        (self.x, self.y, self.z)
+    }
+
+    pub fn zero() -> Self {
+        Self::new(0, 0, 0)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        *self == Self::zero()
+    }
+
+    pub fn abs(&self) -> Self {
+        Self::new(self.x.abs(), self.y.abs(), self.z.abs())
+    }
+
+    pub fn manhattan_dist(&self) -> usize {
+        (self.x.abs() + self.y.abs() + self.z.abs()) as usize
+    }
+
+    pub fn inside_box(&self, lower: impl Into<Vec3D>, upper: impl Into<Vec3D>) -> bool {
+        let (lower, upper) = (lower.into(), upper.into());
+        (lower.x..=upper.x).contains(&self.x)
+            && (lower.y..=upper.y).contains(&self.y)
+            && (lower.z..=upper.z).contains(&self.z)
+    }
+
+    fn neighbors(&self, count: usize) -> impl Iterator<Item = Vec3D> {
+        let pos = *self;
+        NEIGHBOR_OFFSETS[..count].iter().map(move |&offset| pos + offset)
+    }
+
+    /// The 6 positions sharing a face with `self`
+    pub fn orthogonal_neighbors(&self) -> impl Iterator<Item = Vec3D> {
+        self.neighbors(6)
+    }
+
+    /// The 18 positions sharing a face or an edge with `self`
+    pub fn edge_neighbors(&self) -> impl Iterator<Item = Vec3D> {
+        self.neighbors(18)
+    }
+
+    /// The 26 positions sharing a face, an edge or a corner with `self`
+    pub fn all_neighbors(&self) -> impl Iterator<Item = Vec3D> {
+        self.neighbors(26)
+    }
+}
+
+/// Parses the first three numbers in the string, e.g. `1,2,3`, `(1, -2, 3)` or `x=1, y=2, z=3`
+impl FromStr for Vec3D {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (x, y, z) = extract_numbers(s)
+            .next_tuple()
+            .ok_or_else(|| format!("Expected three numbers in {s:?}"))?;
+        Ok(Self::new(x, y, z))
+    }
+}
+
+/// Same as for [`crate::math::Vec2D`], coordinates are assumed to fit into an i64
+impl<T, U, V> From<(T, U, V)> for Vec3D
+where
+    T: num::ToPrimitive,
+    U: num::ToPrimitive,
+    V: num::ToPrimitive,
+{
+    fn from((x, y, z): (T, U, V)) -> Self {
+        Self::new(x.to_i64().unwrap(), y.to_i64().unwrap(), z.to_i64().unwrap())
+    }
+}
+
+impl<T, U, V> From<&(T, U, V)> for Vec3D
+where
+    T: num::ToPrimitive + Copy,
+    U: num::ToPrimitive + Copy,
+    V: num::ToPrimitive + Copy,
+{
+    fn from((x, y, z): &(T, U, V)) -> Self {
+        Self::from((*x, *y, *z))
+    }
+}
+
+impl From<&Vec3D> for Vec3D {
+    fn from(pos: &Vec3D) -> Self {
+        *pos
+    }
+}
+
+impl<T> std::ops::Mul<T> for Vec3D
+where
+    T: num::ToPrimitive + num::Integer,
+{
+    type Output = Vec3D;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        let rhs = rhs.to_i64().unwrap();
+        Vec3D::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl<T> std::ops::MulAssign<T> for Vec3D
+where
+    T: num::ToPrimitive + num::Integer,
+{
+    fn mul_assign(&mut self, rhs: T) {
+        *self = *self * rhs;
+    }
+}
+
+macro_rules! impl_left_mul {
+    ($($t:ty),*) => {
+        $(
+            impl std::ops::Mul<Vec3D> for $t {
+                type Output = Vec3D;
+
+                fn mul(self, rhs: Vec3D) -> Self::Output {
+                    rhs * self
+                }
+            }
+        )*
+    };
+}
+impl_left_mul!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl<T> std::ops::Add<T> for Vec3D
+where
+    T: Into<Vec3D>,
+{
+    type Output = Vec3D;
+
+    fn add(self, rhs: T) -> Self::Output {
+        let rhs = rhs.into();
+        Vec3D::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T> std::ops::AddAssign<T> for Vec3D
+where
+    T: Into<Vec3D>,
+{
+    fn add_assign(&mut self, rhs: T) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T> std::ops::Sub<T> for Vec3D
+where
+    T: Into<Vec3D>,
+{
+    type Output = Vec3D;
+
+    fn sub(self, rhs: T) -> Self::Output {
+        let rhs = rhs.into();
+        Vec3D::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T> std::ops::SubAssign<T> for Vec3D
+where
+    T: Into<Vec3D>,
+{
+    fn sub_assign(&mut self, rhs: T) {
+        *self = *self - rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn arithmetics() {
+        let mut vec = Vec3D::zero() + (-1, 1, 2);
+        assert_eq!(vec, (-1, 1, 2));
+        assert_eq!(vec.xyz_tuple(), (-1, 1, 2));
+
+        vec += (1, 2, 3);
+        assert_eq!(vec, (0, 3, 5));
+        vec -= (1, 2, 3);
+        assert_eq!(vec, (-1, 1, 2));
+        assert!((vec - vec).is_zero());
+
+        vec = 2 * vec * 2;
+        assert_eq!(vec, (-4, 4, 8));
+        vec *= -1;
+        assert_eq!(vec, (4, -4, -8));
+        assert_eq!(vec.manhattan_dist(), 16);
+        assert_eq!(vec.abs(), (4, 4, 8));
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!("1,2,3".parse(), Ok(Vec3D::new(1, 2, 3)));
+        assert_eq!("(1, -2, 3)".parse(), Ok(Vec3D::new(1, -2, 3)));
+        assert_eq!("pos=<-1,2,-3>".parse(), Ok(Vec3D::new(-1, 2, -3)));
+        assert!("1,2".parse::<Vec3D>().is_err());
+    }
+
+    #[test]
+    fn neighbors() {
+        let pos = Vec3D::new(1, 2, 3);
+        let orthogonal: HashSet<_> = pos.orthogonal_neighbors().collect();
+        assert_eq!(orthogonal.len(), 6);
+        assert!(orthogonal.iter().all(|n| (*n - pos).manhattan_dist() == 1));
+
+        let edge: HashSet<_> = pos.edge_neighbors().collect();
+        assert_eq!(edge.len(), 18);
+        assert!(edge.iter().all(|n| (*n - pos).manhattan_dist() <= 2));
+
+        let all: HashSet<_> = pos.all_neighbors().collect();
+        assert_eq!(all.len(), 26);
+        assert!(!all.contains(&pos));
+        assert!(all.iter().all(|n| n.inside_box((0, 1, 2), (2, 3, 4))));
+    }
+}
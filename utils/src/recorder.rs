@@ -0,0 +1,264 @@
+//! Records grid snapshots of a step-by-step simulation for debugging. Only the first frame is
+//! stored in full, every further frame is stored as the list of cells that changed.
+
+use std::fmt::Display;
+use std::path::Path;
+use std::time::Duration;
+
+use itertools::Itertools;
+
+use crate::grid::Grid;
+use crate::image::{self, Rgb};
+use crate::math::Vec2D;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recorder<T> {
+    first: Option<Grid<T>>,
+    last: Option<Grid<T>>,
+    deltas: Vec<Vec<(Vec2D, T)>>,
+    dedup: bool,
+}
+
+impl<T> Default for Recorder<T> {
+    fn default() -> Self {
+        Self { first: None, last: None, deltas: Vec::new(), dedup: true }
+    }
+}
+
+impl<T: Clone + PartialEq> Recorder<T> {
+    /// By default, a frame that is identical to the previous one is not recorded
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn keep_duplicates(mut self) -> Self {
+        self.dedup = false;
+        self
+    }
+
+    /// Returns whether the frame was recorded. Panics if the frame's size differs from the
+    /// previous frames.
+    pub fn push(&mut self, frame: &Grid<T>) -> bool {
+        let Some(last) = &mut self.last else {
+            self.first = Some(frame.clone());
+            self.last = Some(frame.clone());
+            return true;
+        };
+        assert_eq!(
+            (last.num_cols(), last.num_rows()),
+            (frame.num_cols(), frame.num_rows()),
+            "All frames need to have the same size"
+        );
+
+        let delta = frame
+            .iter()
+            .filter(|&(pos, item)| last[pos] != *item)
+            .map(|(pos, item)| (pos, item.clone()))
+            .collect_vec();
+        if delta.is_empty() && self.dedup {
+            return false;
+        }
+
+        for (pos, item) in &delta {
+            last[*pos] = item.clone();
+        }
+        self.deltas.push(delta);
+        true
+    }
+
+    pub fn len(&self) -> usize {
+        self.first.as_ref().map_or(0, |_| self.deltas.len() + 1)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.first.is_none()
+    }
+
+    /// The most recently recorded frame
+    pub fn last(&self) -> Option<&Grid<T>> {
+        self.last.as_ref()
+    }
+
+    /// Reconstructs the n-th frame
+    pub fn frame(&self, n: usize) -> Option<Grid<T>> {
+        self.frames().nth(n)
+    }
+
+    pub fn frames(&self) -> impl Iterator<Item = Grid<T>> + '_ {
+        let mut next = self.first.clone();
+        let mut deltas = self.deltas.iter();
+        std::iter::from_fn(move || {
+            let frame = next.take()?;
+            next = deltas.next().map(|delta| {
+                let mut next = frame.clone();
+                for (pos, item) in delta {
+                    next[*pos] = item.clone();
+                }
+                next
+            });
+            Some(frame)
+        })
+    }
+
+    /// Writes every frame to `dir/frame_00000.ppm`, `dir/frame_00001.ppm`, ...
+    pub fn dump_images(&self, dir: impl AsRef<Path>, color: impl Fn(&T) -> Rgb) {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir).unwrap_or_else(|e| panic!("Failed to create {dir:?}: {e}"));
+        for (i, frame) in self.frames().enumerate() {
+            image::save(dir.join(format!("frame_{i:05}.ppm")), &frame.to_ppm(&color));
+        }
+    }
+}
+
+impl<T: Clone + PartialEq + Display> Recorder<T> {
+    /// Plays all frames in the terminal, redrawing the screen for each one
+    pub fn replay(&self, frames_per_second: f64) {
+        let delay = Duration::from_secs_f64(1.0 / frames_per_second);
+        for (i, frame) in self.frames().enumerate() {
+            println!("\x1b[2J\x1b[H{frame}frame {}/{}", i + 1, self.len());
+            std::thread::sleep(delay);
+        }
+    }
+}
+
+const FRAME_SEPARATOR: &str = "---";
+
+impl Recorder<char> {
+    /// Text log with a `WxH` header and the first frame, followed by the changed cells of each
+    /// further frame, one `x,y c` line per cell. Every further frame starts with a `---` line.
+    pub fn delta_log(&self) -> String {
+        let Some(first) = &self.first else {
+            return String::new();
+        };
+        let mut log = format!("{}x{}\n{first}", first.num_cols(), first.num_rows());
+        for delta in &self.deltas {
+            log += FRAME_SEPARATOR;
+            log += "\n";
+            for (pos, c) in delta {
+                log += &format!("{},{} {c}\n", pos.x, pos.y);
+            }
+        }
+        log
+    }
+
+    /// Parses a log written by [`Recorder::delta_log`]
+    pub fn from_delta_log(log: &str) -> Self {
+        let mut lines = log.lines();
+        let Some(header) = lines.next() else {
+            return Self::new();
+        };
+        let (num_cols, num_rows) = header.split_once('x').unwrap();
+        let (num_cols, num_rows): (usize, usize) =
+            (num_cols.parse().unwrap(), num_rows.parse().unwrap());
+
+        // Read exactly as many rows as the header says, so that a row looking like a separator
+        // is not mistaken for one
+        let first: Grid<char> = lines.by_ref().take(num_rows).map(|line| line.chars()).collect();
+        assert_eq!((first.num_cols(), first.num_rows()), (num_cols, num_rows));
+
+        let rest = lines.collect_vec();
+        let deltas = rest
+            .split(|line| *line == FRAME_SEPARATOR)
+            .skip(1)
+            .map(|block| {
+                block
+                    .iter()
+                    .map(|line| {
+                        let (pos, c) = line.split_once(' ').unwrap();
+                        let (x, y) = pos.split_once(',').unwrap();
+                        let pos = Vec2D::new(x.parse().unwrap(), y.parse().unwrap());
+                        (pos, c.chars().exactly_one().ok().unwrap())
+                    })
+                    .collect_vec()
+            })
+            .collect_vec();
+
+        let mut recorder = Self { first: Some(first), last: None, deltas, dedup: true };
+        recorder.last = recorder.frames().last();
+        recorder
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grid::char_grid;
+
+    use super::*;
+
+    fn frames() -> Vec<Grid<char>> {
+        vec![char_grid("@.\n.."), char_grid("@.\n.."), char_grid(".@\n.."), char_grid(".@\n #")]
+    }
+
+    #[test]
+    fn record() {
+        let mut recorder = Recorder::new();
+        assert!(recorder.is_empty());
+        assert_eq!(
+            frames().iter().map(|frame| recorder.push(frame)).collect_vec(),
+            [true, false, true, true]
+        );
+        assert_eq!(recorder.len(), 3);
+        assert_eq!(recorder.last(), frames().last());
+        assert_eq!(recorder.frame(1), Some(frames()[2].clone()));
+        assert_eq!(recorder.frame(3), None);
+        assert_eq!(
+            recorder.frames().collect_vec(),
+            [frames()[0].clone(), frames()[2].clone(), frames()[3].clone()]
+        );
+
+        let mut recorder = Recorder::new().keep_duplicates();
+        for frame in frames() {
+            recorder.push(&frame);
+        }
+        assert_eq!(recorder.frames().collect_vec(), frames());
+    }
+
+    #[test]
+    fn delta_log() {
+        let mut recorder = Recorder::new().keep_duplicates();
+        for frame in frames() {
+            recorder.push(&frame);
+        }
+        let log = recorder.delta_log();
+        assert_eq!(log, "2x2\n@.\n..\n---\n---\n0,0 .\n1,0 @\n---\n0,1  \n1,1 #\n");
+        assert_eq!(Recorder::from_delta_log(&log).frames().collect_vec(), frames());
+
+        assert!(Recorder::from_delta_log("").is_empty());
+        assert_eq!(Recorder::from_delta_log(&Recorder::new().delta_log()).len(), 0);
+    }
+
+    #[test]
+    fn delta_log_with_separator_row() {
+        let mut recorder = Recorder::new();
+        recorder.push(&char_grid("---\n-.-"));
+        let log = recorder.delta_log();
+        assert_eq!(Recorder::from_delta_log(&log).frames().collect_vec(), [char_grid("---\n-.-")]);
+
+        recorder.push(&char_grid("-|-\n---"));
+        let log = recorder.delta_log();
+        assert_eq!(
+            Recorder::from_delta_log(&log).frames().collect_vec(),
+            recorder.frames().collect_vec()
+        );
+        assert_eq!(Recorder::from_delta_log(&log).len(), 2);
+    }
+
+    #[test]
+    fn dump_images() {
+        let mut recorder = Recorder::new();
+        for frame in frames() {
+            recorder.push(&frame);
+        }
+        let dir = std::env::temp_dir().join(format!("recorder_test_{}", std::process::id()));
+        recorder.dump_images(&dir, |&c| if c == '.' { image::BLACK } else { image::WHITE });
+        let mut files =
+            std::fs::read_dir(&dir).unwrap().map(|entry| entry.unwrap().file_name()).collect_vec();
+        files.sort();
+        assert_eq!(files, ["frame_00000.ppm", "frame_00001.ppm", "frame_00002.ppm"]);
+        assert_eq!(
+            std::fs::read(dir.join("frame_00001.ppm")).unwrap(),
+            frames()[2].to_ppm(|&c| if c == '.' { image::BLACK } else { image::WHITE })
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
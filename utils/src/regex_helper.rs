@@ -1,9 +1,9 @@
-use std::str::FromStr;
+use std::{ops::RangeInclusive, str::FromStr};
 
 use itertools::Itertools;
 use lazy_regex::regex;
 
-use crate::math::Vec2D;
+use crate::math::{Line1D, Vec2D};
 
 pub trait RegexHelper {
     /// shortcut for find_iter followed by a map to str
@@ -48,8 +48,74 @@ where
     })
 }
 
+/// Like [`extract_numbers`], but a leading `-` is treated as a separator instead of a sign, e.g.
+/// `"3-7"` yields `3, 7` instead of `3, -7`
+pub fn extract_unsigned_numbers<T>(s: &str) -> impl Iterator<Item = T> + '_
+where
+    T: num::Integer + FromStr + Clone,
+{
+    regex!(r"\d+").find_iter(s).map(|s| {
+        s.as_str()
+            .parse::<T>()
+            .unwrap_or_else(|_| panic!("Should always be able to parse this regex into a integer"))
+    })
+}
+
+#[deprecated(note = "use `extract_unsigned_numbers::<usize>` instead")]
 pub fn extract_numbers_unsigned(s: &str) -> impl Iterator<Item = usize> + '_ {
-    extract_numbers::<usize>(s)
+    extract_unsigned_numbers::<usize>(s)
+}
+
+fn parse_radix<T: num::Num>(s: &str, prefix_len: usize, radix: u32) -> T {
+    let (sign, digits) = match s.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", s),
+    };
+    T::from_str_radix(&format!("{sign}{}", &digits[prefix_len..]), radix)
+        .unwrap_or_else(|_| panic!("Failed to parse {s} with radix {radix}"))
+}
+
+/// Extracts hexadecimal literals like `0xff` or `-0x1A`
+pub fn extract_hex_numbers<T: num::Num>(s: &str) -> impl Iterator<Item = T> + '_ {
+    regex!(r"-?\b0x[0-9a-fA-F]+\b").find_iter(s).map(|m| parse_radix(m.as_str(), 2, 16))
+}
+
+/// Extracts binary literals like `0b101` or `-0b1`
+pub fn extract_binary_numbers<T: num::Num>(s: &str) -> impl Iterator<Item = T> + '_ {
+    regex!(r"-?\b0b[01]+\b").find_iter(s).map(|m| parse_radix(m.as_str(), 2, 2))
+}
+
+/// Extracts decimal floats, with optional fractional part and exponent (e.g. `-1.5e3`, `.5` or
+/// `1.`)
+pub fn extract_floats<T>(s: &str) -> impl Iterator<Item = T> + '_
+where
+    T: num::Float + FromStr,
+{
+    regex!(r"-?(?:\d+(?:\.\d*)?|\.\d+)(?:[eE][+-]?\d+)?")
+        .find_iter(s)
+        .map(|m| m.as_str().parse().unwrap_or_else(|_| panic!("Failed to parse {}", m.as_str())))
+}
+
+/// Extracts ranges like `3-7` or `-5--3`. Bounds are inclusive.
+pub fn extract_ranges<T>(s: &str) -> impl Iterator<Item = RangeInclusive<T>> + '_
+where
+    T: num::Integer + FromStr,
+{
+    regex!(r"(-?\d+)-(-?\d+)").captures_iter(s).map(|captures| {
+        let (start, end) = captures
+            .iter()
+            .skip(1)
+            .map(|c| c.unwrap().as_str())
+            .map(|s| s.parse::<T>().unwrap_or_else(|_| panic!("Failed to parse {s}")))
+            .collect_tuple()
+            .unwrap();
+        start..=end
+    })
+}
+
+/// Same as [`extract_ranges`], but returns [`Line1D`]s
+pub fn extract_lines(s: &str) -> impl Iterator<Item = Line1D> + '_ {
+    extract_ranges::<i64>(s).map(|range| Line1D::new(range.into_inner()))
 }
 
 pub fn extract_two_numbers(s: &str) -> Vec2D {
@@ -128,6 +194,43 @@ mod tests {
     fn numbers() {
         assert_eq!(vec![123, 456, 789], extract_numbers("123 456 789").collect_vec());
         assert_eq!(vec![-123, 456, -789], extract_numbers("-123   456 -789").collect_vec());
+        assert_eq!(
+            vec![i128::MIN, i128::MAX],
+            extract_numbers(&format!("{}, {}", i128::MIN, i128::MAX)).collect_vec()
+        );
+        assert_eq!(vec![u128::MAX], extract_numbers(&u128::MAX.to_string()).collect_vec());
+
+        assert_eq!(vec![3, 7, 10], extract_unsigned_numbers::<u8>("3-7,-10").collect_vec());
+    }
+
+    #[test]
+    fn radix_numbers() {
+        assert_eq!(vec![255, -26, 0], extract_hex_numbers::<i64>("0xff, -0x1A 0x0").collect_vec());
+        assert_eq!(vec![5_u8, 1], extract_binary_numbers("a=0b101 b=0b1 c=12").collect_vec());
+        assert_eq!(vec![-1], extract_binary_numbers::<i32>("-0b1").collect_vec());
+
+        // Literals have to stand on their own, not be part of a longer word or number
+        assert_eq!(vec![2], extract_hex_numbers::<u8>("a0xff 0x1g 0x2").collect_vec());
+        assert_eq!(vec![3], extract_binary_numbers::<u8>("x0b1 0b12 0b11").collect_vec());
+    }
+
+    #[test]
+    fn floats() {
+        assert_eq!(
+            vec![1.5, -2.0, 3.0, -1500.0, 0.25],
+            extract_floats::<f64>("1.5 -2 3, -1.5e3 x=2.5E-1").collect_vec()
+        );
+        assert_eq!(vec![0.5, -0.25, 1.0], extract_floats::<f64>("x=.5 y=-.25 z=1.").collect_vec());
+    }
+
+    #[test]
+    fn ranges() {
+        assert_eq!(vec![3..=7, 10..=12], extract_ranges::<usize>("3-7,10-12").collect_vec());
+        assert_eq!(vec![-5..=-3, -1..=2], extract_ranges::<i32>("-5--3 -1-2").collect_vec());
+        assert_eq!(
+            vec![Line1D::new((2, 4)), Line1D::new((6, 8))],
+            extract_lines("2-4,6-8").collect_vec()
+        );
     }
 
     #[test]
@@ -1,10 +1,12 @@
 use std::{
     collections::HashMap,
+    fmt::Display,
     hash::Hash,
     ops::{Deref, DerefMut},
 };
 
-use crate::math::Vec2D;
+use crate::grid::Grid;
+use crate::math::{Box2D, Vec2D};
 
 #[derive(Debug, PartialEq, Eq, Clone, Default, derive_more::From)]
 pub struct SparseGrid<T> {
@@ -50,27 +52,99 @@ where
     pub fn orthogonal_neighbors<'a, 'b: 'a>(
         &'a self,
         pos: &'b Vec2D,
-    ) -> impl Iterator<Item = (&'a Vec2D, &'a T)> + 'a {
-        pos.orthogonal_neighbors().flat_map(|pos| self.get(pos)).map(move |val| (pos, val))
+    ) -> impl Iterator<Item = (Vec2D, &'a T)> + 'a {
+        pos.orthogonal_neighbors().filter_map(|n| self.get(n).map(|val| (n, val)))
     }
 
     pub fn diagonal_neighbors<'a, 'b: 'a>(
         &'a self,
         pos: &'b Vec2D,
-    ) -> impl Iterator<Item = (&'a Vec2D, &'a T)> + 'a {
-        pos.diagonal_neighbors().flat_map(|pos| self.get(pos)).map(move |val| (pos, val))
+    ) -> impl Iterator<Item = (Vec2D, &'a T)> + 'a {
+        pos.diagonal_neighbors().filter_map(|n| self.get(n).map(|val| (n, val)))
     }
 
     pub fn all_neighbors<'a, 'b: 'a>(
         &'a self,
         pos: &'b Vec2D,
-    ) -> impl Iterator<Item = (&'a Vec2D, &'a T)> + 'a {
-        pos.all_neighbors().flat_map(|pos| self.get(pos)).map(move |val| (pos, val))
+    ) -> impl Iterator<Item = (Vec2D, &'a T)> + 'a {
+        pos.all_neighbors().filter_map(|n| self.get(n).map(|val| (n, val)))
+    }
+}
+
+impl<T> SparseGrid<T> {
+    /// Smallest box containing all cells, `None` if the grid is empty
+    pub fn bounds(&self) -> Option<Box2D> {
+        (!self.data.is_empty()).then(|| self.data.keys().copied().collect())
+    }
+
+    /// Dense copy of the cells inside [`SparseGrid::bounds`], with missing cells set to
+    /// `default`. The dense grid's `(0, 0)` is the lower corner of the bounds.
+    pub fn to_dense(&self, default: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let Some(Box2D { lower, upper }) = self.bounds() else {
+            return Grid::from_flat(Vec::new(), 0);
+        };
+        let default = &default;
+        (lower.y..=upper.y)
+            .map(|y| {
+                (lower.x..=upper.x).map(move |x| {
+                    self.data.get(&Vec2D::new(x, y)).cloned().unwrap_or_else(|| default.clone())
+                })
+            })
+            .collect()
+    }
+
+    /// Draws the cells inside [`SparseGrid::bounds`], with `fill` for missing cells
+    pub fn display_with(&self, fill: char) -> impl Display + '_
+    where
+        T: Display,
+    {
+        SparseGridDisplay { grid: self, fill }
+    }
+}
+
+struct SparseGridDisplay<'a, T> {
+    grid: &'a SparseGrid<T>,
+    fill: char,
+}
+
+impl<T: Display> Display for SparseGridDisplay<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some(Box2D { lower, upper }) = self.grid.bounds() else {
+            return Ok(());
+        };
+        for y in lower.y..=upper.y {
+            for x in lower.x..=upper.x {
+                match self.grid.data.get(&Vec2D::new(x, y)) {
+                    Some(val) => write!(f, "{val}")?,
+                    None => write!(f, "{}", self.fill)?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Missing cells are drawn as `.`, see [`SparseGrid::display_with`] for other fill characters
+impl<T: Display> Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_with('.'))
+    }
+}
+
+impl<T: Clone> From<&Grid<T>> for SparseGrid<T> {
+    fn from(grid: &Grid<T>) -> Self {
+        Self::from(grid.iter().map(|(pos, val)| (pos, val.clone())).collect::<HashMap<_, _>>())
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use itertools::Itertools;
+
     use super::*;
 
     #[test]
@@ -86,4 +160,43 @@ mod tests {
         assert_eq!(grid.get((0, 2)), None);
         assert_eq!(grid.get((2, 2)), None);
     }
+
+    #[test]
+    fn neighbors() {
+        let grid: SparseGrid<_> =
+            [((0, 0), 'a'), ((1, 0), 'b'), ((1, 1), 'c')].into_iter().collect();
+
+        assert_eq!(
+            grid.orthogonal_neighbors(&Vec2D::new(1, 0)).sorted().collect_vec(),
+            [(Vec2D::new(0, 0), &'a'), (Vec2D::new(1, 1), &'c')]
+        );
+        assert_eq!(
+            grid.diagonal_neighbors(&Vec2D::new(0, 0)).collect_vec(),
+            [(Vec2D::new(1, 1), &'c')]
+        );
+        assert_eq!(grid.all_neighbors(&Vec2D::new(0, 1)).count(), 3);
+    }
+
+    #[test]
+    fn bounds_and_dense() {
+        let grid: SparseGrid<_> = [((-1, 2), 'a'), ((1, 3), 'b')].into_iter().collect();
+        assert_eq!(grid.bounds(), Some(Box2D::new((-1, 2), (1, 3))));
+        assert_eq!(grid.to_dense(' '), Grid::from([['a', ' ', ' '], [' ', ' ', 'b']]));
+        assert_eq!(grid.to_string(), "a..\n..b\n");
+        assert_eq!(grid.display_with('#').to_string(), "a##\n##b\n");
+
+        let empty = SparseGrid::<char>::new();
+        assert_eq!(empty.bounds(), None);
+        assert_eq!(empty.to_dense(' ').num_rows(), 0);
+        assert_eq!(empty.to_string(), "");
+    }
+
+    #[test]
+    fn from_grid() {
+        let grid: Grid<_> = [[1, 2], [3, 4]].into();
+        let sparse = SparseGrid::from(&grid);
+        assert_eq!(sparse.len(), 4);
+        assert_eq!(sparse.get((1, 1)), Some(&4));
+        assert_eq!(sparse.to_dense(0), grid);
+    }
 }